@@ -0,0 +1,362 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::environment::find_collection_root;
+use crate::glob::glob_match;
+use crate::project::ProjectConfig;
+
+const DEFAULT_IGNORE: [&str; 2] = ["node_modules", ".git"];
+
+#[derive(Debug, Clone)]
+pub struct Collection {
+    pub root: PathBuf,
+    pub config: CollectionConfig,
+    pub project: ProjectConfig,
+}
+
+// Mirrors bruno.json; not every field affects the generated command yet.
+#[allow(dead_code)]
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionConfig {
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub ignore: Option<Vec<String>>,
+    #[serde(default)]
+    pub scripts: ScriptsConfig,
+    #[serde(default)]
+    pub presets: PresetsConfig,
+    pub proxy: Option<ProxyConfig>,
+    pub client_certificates: Option<ClientCertificates>,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptsConfig {
+    #[serde(default)]
+    pub module_whitelist: Vec<String>,
+    #[serde(default)]
+    pub filesystem_access: FilesystemAccess,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct FilesystemAccess {
+    #[serde(default)]
+    pub allow: bool,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresetsConfig {
+    pub request_type: Option<String>,
+    pub request_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxyConfig {
+    #[serde(default)]
+    pub enabled: ProxyEnabled,
+    pub protocol: Option<String>,
+    pub hostname: Option<String>,
+    pub port: Option<u16>,
+    pub auth: Option<ProxyAuth>,
+    pub bypass_proxy: Option<String>,
+}
+
+impl ProxyConfig {
+    /// Calls `visit` on every text field that may hold `{{variables}}`, with
+    /// a short description of the field.
+    pub fn visit_text_mut<F>(&mut self, visit: &mut F) -> Result<(), String>
+    where
+        F: FnMut(&str, &mut String) -> Result<(), String>,
+    {
+        let auth = self.auth.as_mut();
+        let (username, password) = match auth {
+            Some(auth) => (auth.username.as_mut(), auth.password.as_mut()),
+            None => (None, None),
+        };
+        let fields = [
+            ("proxy protocol", self.protocol.as_mut()),
+            ("proxy hostname", self.hostname.as_mut()),
+            ("proxy username", username),
+            ("proxy password", password),
+            ("proxy bypass list", self.bypass_proxy.as_mut()),
+        ];
+        for (location, text) in fields {
+            if let Some(text) = text {
+                visit(location, text)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ProxyEnabled {
+    On,
+    Off,
+    #[default]
+    Global,
+}
+
+impl<'de> Deserialize<'de> for ProxyEnabled {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match serde_json::Value::deserialize(deserializer)? {
+            serde_json::Value::Bool(true) => Ok(ProxyEnabled::On),
+            serde_json::Value::Bool(false) => Ok(ProxyEnabled::Off),
+            serde_json::Value::String(s) if s == "global" => Ok(ProxyEnabled::Global),
+            other => Err(serde::de::Error::custom(format!("invalid proxy.enabled: {}", other))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProxyAuth {
+    #[serde(default)]
+    pub enabled: bool,
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ClientCertificates {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub certs: Vec<ClientCertificate>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientCertificate {
+    pub domain: String,
+    #[serde(rename = "type", default = "default_cert_type")]
+    pub cert_type: String,
+    pub cert_file_path: Option<String>,
+    pub key_file_path: Option<String>,
+    pub pfx_file_path: Option<String>,
+    pub passphrase: Option<String>,
+}
+
+impl ClientCertificates {
+    /// Calls `visit` on every text field that may hold `{{variables}}`, with
+    /// a short description of the field.
+    pub fn visit_text_mut<F>(&mut self, visit: &mut F) -> Result<(), String>
+    where
+        F: FnMut(&str, &mut String) -> Result<(), String>,
+    {
+        for cert in &mut self.certs {
+            visit("client certificate domain", &mut cert.domain)?;
+            let fields = [
+                ("client certificate file", cert.cert_file_path.as_mut()),
+                ("client key file", cert.key_file_path.as_mut()),
+                ("client PFX file", cert.pfx_file_path.as_mut()),
+                ("client certificate passphrase", cert.passphrase.as_mut()),
+            ];
+            for (location, text) in fields {
+                if let Some(text) = text {
+                    visit(location, text)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn default_cert_type() -> String {
+    "cert".to_string()
+}
+
+impl Collection {
+    pub fn load(bru_file_path: &Path) -> Result<Collection, String> {
+        let root = find_collection_root(bru_file_path)?;
+        let config_path = root.join("bruno.json");
+
+        // bruno.json only adds settings, so a broken one should not stop a run.
+        let config = if config_path.exists() {
+            fs::read_to_string(&config_path)
+                .map_err(|e| format!("Cannot read bruno.json: {}", e))
+                .and_then(|content| parse_collection_config(&content))
+                .unwrap_or_else(|e| {
+                    eprintln!("Warning: {}; ignoring its settings", e);
+                    CollectionConfig::default()
+                })
+        } else {
+            CollectionConfig::default()
+        };
+
+        let project = ProjectConfig::load(&root)?;
+
+        Ok(Collection { root, config, project })
+    }
+
+    #[allow(dead_code)]
+    pub fn display_name(&self) -> String {
+        match &self.config.name {
+            Some(name) => name.clone(),
+            None => self.root.display().to_string(),
+        }
+    }
+
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        let relative = path.strip_prefix(&self.root).unwrap_or(path);
+        self.config.is_ignored(relative)
+    }
+}
+
+impl CollectionConfig {
+    pub fn ignore_patterns(&self) -> Vec<&str> {
+        match &self.ignore {
+            Some(patterns) => patterns.iter().map(String::as_str).collect(),
+            None => DEFAULT_IGNORE.to_vec(),
+        }
+    }
+
+    pub fn is_ignored(&self, relative: &Path) -> bool {
+        let patterns = self.ignore_patterns();
+        let full = relative.to_string_lossy().replace('\\', "/");
+
+        let components: Vec<String> = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned())
+            .collect();
+
+        patterns.iter().any(|pattern| {
+            let pattern = pattern.trim_matches('/');
+            if pattern.contains('/') {
+                // A pattern with a path in it matches that folder and everything under it.
+                (1..=components.len()).any(|n| glob_match(pattern, &components[..n].join("/")))
+            } else {
+                glob_match(pattern, &full) || components.iter().any(|c| glob_match(pattern, c))
+            }
+        })
+    }
+}
+
+pub fn parse_collection_config(content: &str) -> Result<CollectionConfig, String> {
+    if content.trim().is_empty() {
+        return Ok(CollectionConfig::default());
+    }
+    serde_json::from_str(content).map_err(|e| format!("Invalid bruno.json: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn parse_full_config() {
+        let content = r#"{
+  "version": "1",
+  "name": "Orders API",
+  "type": "collection",
+  "ignore": ["node_modules", ".git", "drafts"],
+  "scripts": { "moduleWhitelist": ["crypto"], "filesystemAccess": { "allow": true } },
+  "presets": { "requestType": "http", "requestUrl": "https://api.example.com" },
+  "proxy": {
+    "enabled": "global",
+    "protocol": "http",
+    "hostname": "proxy.corp",
+    "port": 3128,
+    "auth": { "enabled": true, "username": "bob", "password": "pw" },
+    "bypassProxy": "localhost"
+  },
+  "clientCertificates": {
+    "enabled": true,
+    "certs": [{ "domain": "*.example.com", "type": "pfx", "pfxFilePath": "certs/c.pfx", "passphrase": "x" }]
+  }
+}"#;
+        let config = parse_collection_config(content).unwrap();
+        assert_eq!(config.name.as_deref(), Some("Orders API"));
+        assert_eq!(config.version.as_deref(), Some("1"));
+        assert_eq!(config.ignore_patterns(), vec!["node_modules", ".git", "drafts"]);
+        let proxy = config.proxy.unwrap();
+        assert_eq!(proxy.enabled, ProxyEnabled::Global);
+        assert_eq!(proxy.port, Some(3128));
+        let certs = config.client_certificates.unwrap();
+        assert_eq!(certs.certs[0].cert_type, "pfx");
+        assert_eq!(config.scripts.module_whitelist, vec!["crypto"]);
+        assert_eq!(config.presets.request_type.as_deref(), Some("http"));
+    }
+
+    #[test]
+    fn empty_config_uses_defaults() {
+        let config = parse_collection_config("{}").unwrap();
+        assert_eq!(config.ignore_patterns(), vec!["node_modules", ".git"]);
+        assert!(config.proxy.is_none());
+    }
+
+    #[test]
+    fn invalid_config_is_an_error() {
+        let result = parse_collection_config("{ not json");
+        assert!(result.unwrap_err().contains("Invalid bruno.json"));
+    }
+
+    #[test]
+    fn ignored_paths() {
+        let config = parse_collection_config(r#"{ "ignore": ["drafts", "*.tmp.bru"] }"#).unwrap();
+        assert!(config.is_ignored(Path::new("drafts/request.bru")));
+        assert!(config.is_ignored(Path::new("users/old.tmp.bru")));
+        assert!(!config.is_ignored(Path::new("users/get.bru")));
+        assert!(!config.is_ignored(Path::new("node_modules/x.bru")));
+    }
+
+    #[test]
+    fn ignored_nested_paths() {
+        let config = parse_collection_config(r#"{ "ignore": ["drafts/old"] }"#).unwrap();
+        assert!(config.is_ignored(Path::new("drafts/old")));
+        assert!(config.is_ignored(Path::new("drafts/old/req.bru")));
+        assert!(config.is_ignored(Path::new("drafts/old/v1/req.bru")));
+        assert!(!config.is_ignored(Path::new("drafts/new/req.bru")));
+        assert!(!config.is_ignored(Path::new("other/drafts/old/req.bru")));
+    }
+
+    #[test]
+    fn load_collection_from_nested_request() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("bruno.json"), r#"{ "name": "Demo" }"#).unwrap();
+        let dir = temp.path().join("users");
+        fs::create_dir_all(&dir).unwrap();
+
+        let collection = Collection::load(&dir.join("get.bru")).unwrap();
+        assert_eq!(collection.root, temp.path());
+        assert_eq!(collection.display_name(), "Demo");
+        assert!(collection.is_ignored(&temp.path().join(".git/x.bru")));
+    }
+
+    #[test]
+    fn malformed_config_is_ignored() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("bruno.json"), "{ not json").unwrap();
+
+        let collection = Collection::load(&temp.path().join("get.bru")).unwrap();
+        assert!(collection.config.proxy.is_none());
+        assert_eq!(collection.config.ignore_patterns(), vec!["node_modules", ".git"]);
+    }
+
+    #[test]
+    fn proxy_text_fields_are_visited() {
+        let config = parse_collection_config(
+            r#"{ "proxy": { "enabled": true, "hostname": "{{PROXY_HOST}}", "auth": { "enabled": true, "password": "{{PROXY_PASS}}" } } }"#,
+        )
+        .unwrap();
+        let mut proxy = config.proxy.unwrap();
+        let mut seen = Vec::new();
+        proxy
+            .visit_text_mut(&mut |location, text| {
+                seen.push(location.to_string());
+                *text = text.to_lowercase();
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(seen, vec!["proxy hostname", "proxy password"]);
+        assert_eq!(proxy.hostname.as_deref(), Some("{{proxy_host}}"));
+    }
+}
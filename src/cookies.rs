@@ -0,0 +1,155 @@
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::project::CookieDefaults;
+
+/// One line of a Netscape cookie file, as written by curl `-c` and browsers'
+/// cookies.txt exporters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CookieEntry {
+    pub domain: String,
+    pub include_subdomains: bool,
+    pub path: String,
+    pub secure: bool,
+    /// Unix time; 0 for a session cookie.
+    pub expires: u64,
+    pub name: String,
+    pub value: String,
+}
+
+impl CookieEntry {
+    /// Whether a browser would send this cookie to `url` at time `now`.
+    pub fn matches(&self, url: &str, now: u64) -> bool {
+        let (scheme, rest) = url.split_once("://").unwrap_or(("http", url));
+        let (authority, path) = match rest.find(['/', '?', '#']) {
+            Some(pos) => (&rest[..pos], &rest[pos..]),
+            None => (rest, "/"),
+        };
+        let authority = authority.rsplit_once('@').map(|(_, host)| host).unwrap_or(authority);
+        let host = authority.split(':').next().unwrap_or_default().to_lowercase();
+        let path = path.split(['?', '#']).next().filter(|p| !p.is_empty()).unwrap_or("/");
+
+        let domain = self.domain.trim_start_matches('.').to_lowercase();
+        let domain_ok = host == domain || (self.include_subdomains && host.ends_with(&format!(".{}", domain)));
+        let path_ok = path_matches(path, &self.path);
+        let secure_ok = !self.secure || scheme.eq_ignore_ascii_case("https");
+        let fresh = self.expires == 0 || self.expires > now;
+
+        domain_ok && path_ok && secure_ok && fresh
+    }
+}
+
+/// The RFC 6265 path match: `/v1` covers `/v1` and `/v1/users`, not `/v10`.
+fn path_matches(request_path: &str, cookie_path: &str) -> bool {
+    match request_path.strip_prefix(cookie_path) {
+        Some(rest) => rest.is_empty() || cookie_path.ends_with('/') || rest.starts_with('/'),
+        None => false,
+    }
+}
+
+pub fn parse_netscape(content: &str) -> Vec<CookieEntry> {
+    content
+        .lines()
+        .filter_map(|line| {
+            // curl marks HttpOnly cookies with a prefix on an otherwise normal line.
+            let line = line.strip_prefix("#HttpOnly_").unwrap_or(line);
+            if line.trim().is_empty() || line.starts_with('#') {
+                return None;
+            }
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() < 7 {
+                return None;
+            }
+            Some(CookieEntry {
+                domain: fields[0].to_string(),
+                include_subdomains: fields[1].eq_ignore_ascii_case("TRUE"),
+                path: fields[2].to_string(),
+                secure: fields[3].eq_ignore_ascii_case("TRUE"),
+                expires: fields[4].parse().unwrap_or(0),
+                name: fields[5].to_string(),
+                value: fields[6..].join("\t"),
+            })
+        })
+        .collect()
+}
+
+/// Collects the cookies `.bruq.json` configures for `request_url`: those in
+/// its cookie file that apply to the URL, then its fixed values.
+pub fn load_cookies(
+    defaults: &CookieDefaults,
+    collection_root: &Path,
+    request_url: &str,
+) -> Result<Vec<(String, String)>, String> {
+    let mut cookies = Vec::new();
+
+    if let Some(file) = &defaults.file {
+        let path = collection_root.join(file);
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Cannot read cookie file {:?}: {}", path, e))?;
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        cookies.extend(
+            parse_netscape(&content)
+                .into_iter()
+                .filter(|entry| entry.matches(request_url, now))
+                .map(|entry| (entry.name, entry.value)),
+        );
+    }
+
+    for (name, value) in &defaults.values {
+        cookies.retain(|(existing, _)| existing != name);
+        cookies.push((name.clone(), value.clone()));
+    }
+
+    Ok(cookies)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const JAR: &str = "# Netscape HTTP Cookie File\n\
+        .example.com\tTRUE\t/\tFALSE\t0\ttheme\tdark\n\
+        #HttpOnly_api.example.com\tFALSE\t/v1\tTRUE\t4102444800\tsession\tabc\n\
+        api.example.com\tFALSE\t/\tFALSE\t1000\told\tgone\n";
+
+    #[test]
+    fn parses_netscape_lines() {
+        let entries = parse_netscape(JAR);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[1].domain, "api.example.com");
+        assert!(entries[1].secure);
+        assert_eq!(entries[1].expires, 4102444800);
+        assert_eq!((entries[1].name.as_str(), entries[1].value.as_str()), ("session", "abc"));
+    }
+
+    #[test]
+    fn matches_domain_path_scheme_and_expiry() {
+        let entries = parse_netscape(JAR);
+        let now = 2000;
+        let names = |url: &str| -> Vec<&str> {
+            entries.iter().filter(|e| e.matches(url, now)).map(|e| e.name.as_str()).collect()
+        };
+
+        assert_eq!(names("https://api.example.com/v1/users"), vec!["theme", "session"]);
+        assert_eq!(names("http://api.example.com/v1/users"), vec!["theme"]);
+        assert_eq!(names("https://api.example.com/v2"), vec!["theme"]);
+        assert_eq!(names("https://api.example.com/v1"), vec!["theme", "session"]);
+        assert_eq!(names("https://api.example.com/v10"), vec!["theme"]);
+        assert!(names("https://example.org/").is_empty());
+    }
+
+    #[test]
+    fn config_values_override_file_cookies() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("cookies.txt"), JAR).unwrap();
+        let defaults = CookieDefaults {
+            file: Some("cookies.txt".to_string()),
+            values: [("theme".to_string(), "light".to_string())].into(),
+        };
+
+        let cookies = load_cookies(&defaults, temp.path(), "https://api.example.com/v1").unwrap();
+        assert_eq!(cookies, vec![("session".to_string(), "abc".to_string()), ("theme".to_string(), "light".to_string())]);
+    }
+}
@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+
+pub fn parse_dotenv(content: &str) -> HashMap<String, String> {
+    let mut vars = HashMap::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+
+        let key = key.trim();
+        if key.is_empty() {
+            continue;
+        }
+
+        vars.insert(key.to_string(), parse_value(value.trim()));
+    }
+
+    vars
+}
+
+fn parse_value(value: &str) -> String {
+    if let Some(inner) = value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')) {
+        return inner.to_string();
+    }
+
+    if let Some(inner) = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        return unescape_double_quoted(inner);
+    }
+
+    match value.find(" #") {
+        Some(pos) => value[..pos].trim_end().to_string(),
+        None => value.to_string(),
+    }
+}
+
+fn unescape_double_quoted(value: &str) -> String {
+    let mut result = String::new();
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some('r') => result.push('\r'),
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_plain_and_quoted_values() {
+        let content = r#"
+# comment
+API_KEY=abc123
+export TOKEN = "line1\nline2"
+RAW='keep \n as is'
+URL=http://localhost:3000 # trailing comment
+"#;
+        let vars = parse_dotenv(content);
+        assert_eq!(vars.get("API_KEY").unwrap(), "abc123");
+        assert_eq!(vars.get("TOKEN").unwrap(), "line1\nline2");
+        assert_eq!(vars.get("RAW").unwrap(), "keep \\n as is");
+        assert_eq!(vars.get("URL").unwrap(), "http://localhost:3000");
+    }
+
+    #[test]
+    fn skip_malformed_lines() {
+        let vars = parse_dotenv("NOT_A_PAIR\n=value\nOK=1\n");
+        assert_eq!(vars.len(), 1);
+        assert_eq!(vars.get("OK").unwrap(), "1");
+    }
+}
@@ -0,0 +1,221 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// 2024-01-01T00:00:00Z, used as the clock when output must be reproducible.
+const SEEDED_EPOCH_MILLIS: u64 = 1_704_067_200_000;
+
+const FIRST_NAMES: &[&str] = &[
+    "Alice", "Bob", "Carol", "David", "Emma", "Frank", "Grace", "Henry", "Irene", "Jack",
+    "Laura", "Mason", "Nina", "Oscar", "Paula", "Quinn", "Rosa", "Samuel", "Tina", "Victor",
+];
+const LAST_NAMES: &[&str] = &[
+    "Anderson", "Brown", "Clark", "Davis", "Evans", "Garcia", "Harris", "Johnson", "King", "Lewis",
+    "Martin", "Nelson", "Parker", "Robinson", "Smith", "Taylor", "Walker", "White", "Young", "Zhang",
+];
+const DOMAINS: &[&str] = &["example.com", "example.net", "example.org", "test.dev", "mail.test"];
+const CITIES: &[&str] = &[
+    "Amsterdam", "Berlin", "Chicago", "Denver", "Lisbon", "Madrid", "Oslo", "Prague", "Toronto", "Vienna",
+];
+const COUNTRIES: &[(&str, &str)] = &[
+    ("Germany", "DE"), ("France", "FR"), ("Spain", "ES"), ("Canada", "CA"), ("Japan", "JP"),
+    ("Brazil", "BR"), ("Poland", "PL"), ("Norway", "NO"), ("Portugal", "PT"), ("United States", "US"),
+];
+const STREETS: &[&str] = &["Main St", "Oak Ave", "Maple Rd", "Cedar Ln", "Park Blvd", "Elm St"];
+const WORDS: &[&str] = &[
+    "alpha", "bridge", "cloud", "delta", "ember", "forest", "granite", "harbor", "island", "jungle",
+    "kernel", "lumen", "meadow", "nectar", "orbit", "prism", "quartz", "river", "summit", "timber",
+];
+const JOB_TITLES: &[&str] = &[
+    "Software Engineer", "Product Manager", "Data Analyst", "Designer", "Support Specialist",
+    "Account Executive", "QA Engineer", "Site Reliability Engineer",
+];
+const COLORS: &[&str] = &["red", "green", "blue", "orange", "purple", "teal", "yellow", "black"];
+const PROTOCOLS: &[&str] = &["http", "https"];
+const LOCALES: &[&str] = &["en", "de", "fr", "es", "pt", "ja", "pl", "nl"];
+const USER_AGENTS: &[&str] = &[
+    "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0 Safari/537.36",
+    "Mozilla/5.0 (Macintosh; Intel Mac OS X 14_2) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.2 Safari/605.1.15",
+    "Mozilla/5.0 (X11; Linux x86_64; rv:121.0) Gecko/20100101 Firefox/121.0",
+];
+const ALPHANUMERIC: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+
+/// Source of Bruno's `{{$name}}` dynamic values. Each call to `resolve`
+/// produces a fresh value; a seed makes the sequence (and the clock) repeatable.
+pub struct DynamicVars {
+    state: u64,
+    now_millis: u64,
+}
+
+impl DynamicVars {
+    pub fn new(seed: Option<u64>) -> DynamicVars {
+        match seed {
+            Some(seed) => DynamicVars { state: seed, now_millis: SEEDED_EPOCH_MILLIS },
+            None => {
+                let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+                DynamicVars {
+                    state: now.as_nanos() as u64 ^ ((std::process::id() as u64) << 32),
+                    now_millis: now.as_millis() as u64,
+                }
+            }
+        }
+    }
+
+    pub fn resolve(&mut self, name: &str) -> Option<String> {
+        let value = match name {
+            "guid" | "randomUUID" => self.uuid(),
+            "timestamp" => (self.now_millis / 1000).to_string(),
+            "isoTimestamp" => format_iso8601(self.now_millis),
+            "randomInt" => self.below(1001).to_string(),
+            "randomBoolean" => (self.next() & 1 == 1).to_string(),
+            "randomAlphaNumeric" => (ALPHANUMERIC[self.below(ALPHANUMERIC.len() as u64) as usize] as char).to_string(),
+            "randomHexColor" => format!("#{:06x}", self.below(0x100_0000)),
+            "randomColor" => self.pick(COLORS).to_string(),
+            "randomIP" => format!("{}.{}.{}.{}", self.below(256), self.below(256), self.below(256), self.below(256)),
+            "randomIPV6" => (0..8).map(|_| format!("{:x}", self.below(0x10000))).collect::<Vec<_>>().join(":"),
+            "randomMACAddress" => (0..6).map(|_| format!("{:02x}", self.below(256))).collect::<Vec<_>>().join(":"),
+            "randomPassword" => (0..15).map(|_| ALPHANUMERIC[self.below(ALPHANUMERIC.len() as u64) as usize] as char).collect(),
+            "randomLocale" => self.pick(LOCALES).to_string(),
+            "randomUserAgent" => self.pick(USER_AGENTS).to_string(),
+            "randomProtocol" => self.pick(PROTOCOLS).to_string(),
+            "randomSemver" => format!("{}.{}.{}", self.below(10), self.below(10), self.below(10)),
+            "randomFirstName" => self.pick(FIRST_NAMES).to_string(),
+            "randomLastName" => self.pick(LAST_NAMES).to_string(),
+            "randomFullName" => format!("{} {}", self.pick(FIRST_NAMES), self.pick(LAST_NAMES)),
+            "randomJobTitle" => self.pick(JOB_TITLES).to_string(),
+            "randomUserName" => format!("{}.{}{}", self.pick(FIRST_NAMES), self.pick(LAST_NAMES), self.below(100)).to_lowercase(),
+            "randomEmail" | "randomExampleEmail" => format!(
+                "{}.{}@{}",
+                self.pick(FIRST_NAMES).to_lowercase(),
+                self.pick(LAST_NAMES).to_lowercase(),
+                self.pick(DOMAINS)
+            ),
+            "randomDomainName" => format!("{}.{}", self.pick(WORDS), self.pick(&["com", "net", "org", "io"])),
+            "randomUrl" => format!("https://{}.{}", self.pick(WORDS), self.pick(&["com", "net", "org", "io"])),
+            "randomPhoneNumber" => format!("{:03}-{:03}-{:04}", 200 + self.below(800), self.below(1000), self.below(10000)),
+            "randomCity" => self.pick(CITIES).to_string(),
+            "randomStreetAddress" => format!("{} {}", 1 + self.below(9999), self.pick(STREETS)),
+            "randomCountry" => self.pick(COUNTRIES).0.to_string(),
+            "randomCountryCode" => self.pick(COUNTRIES).1.to_string(),
+            "randomLatitude" => format!("{:.4}", self.unit() * 180.0 - 90.0),
+            "randomLongitude" => format!("{:.4}", self.unit() * 360.0 - 180.0),
+            "randomWord" => self.pick(WORDS).to_string(),
+            "randomWords" => {
+                let count = 2 + self.below(3);
+                (0..count).map(|_| self.pick(WORDS)).collect::<Vec<_>>().join(" ")
+            }
+            "randomLoremSentence" => {
+                let count = 4 + self.below(5);
+                let words: Vec<&str> = (0..count).map(|_| self.pick(WORDS)).collect();
+                let sentence = words.join(" ");
+                let mut chars = sentence.chars();
+                match chars.next() {
+                    Some(first) => format!("{}{}.", first.to_uppercase(), chars.as_str()),
+                    None => sentence,
+                }
+            }
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    fn next(&mut self) -> u64 {
+        // splitmix64
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next() % bound
+    }
+
+    fn unit(&mut self) -> f64 {
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.below(items.len() as u64) as usize]
+    }
+
+    fn uuid(&mut self) -> String {
+        let high = self.next();
+        let low = self.next();
+        let mut bytes = [0u8; 16];
+        bytes[..8].copy_from_slice(&high.to_be_bytes());
+        bytes[8..].copy_from_slice(&low.to_be_bytes());
+        bytes[6] = (bytes[6] & 0x0f) | 0x40;
+        bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+        let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+        format!("{}-{}-{}-{}-{}", &hex[0..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..32])
+    }
+}
+
+fn format_iso8601(millis: u64) -> String {
+    let secs = millis / 1000;
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+
+    // Civil-from-days, Howard Hinnant's algorithm.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        rem / 3600,
+        (rem % 3600) / 60,
+        rem % 60,
+        millis % 1000
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeded_values_are_reproducible() {
+        let mut a = DynamicVars::new(Some(42));
+        let mut b = DynamicVars::new(Some(42));
+        for name in ["guid", "randomInt", "randomEmail", "randomFullName"] {
+            assert_eq!(a.resolve(name), b.resolve(name));
+        }
+    }
+
+    #[test]
+    fn each_call_yields_a_fresh_value() {
+        let mut dynamic = DynamicVars::new(Some(7));
+        assert_ne!(dynamic.resolve("guid"), dynamic.resolve("guid"));
+    }
+
+    #[test]
+    fn guid_has_uuid_v4_shape() {
+        let guid = DynamicVars::new(Some(1)).resolve("guid").unwrap();
+        assert_eq!(guid.len(), 36);
+        assert_eq!(&guid[14..15], "4");
+        assert!(guid.chars().all(|c| c == '-' || c.is_ascii_hexdigit()));
+    }
+
+    #[test]
+    fn seeded_clock() {
+        let mut dynamic = DynamicVars::new(Some(0));
+        assert_eq!(dynamic.resolve("timestamp").unwrap(), "1704067200");
+        assert_eq!(dynamic.resolve("isoTimestamp").unwrap(), "2024-01-01T00:00:00.000Z");
+    }
+
+    #[test]
+    fn unknown_name() {
+        assert!(DynamicVars::new(None).resolve("nope").is_none());
+    }
+}
@@ -2,9 +2,17 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::fs;
 
-use crate::parser::{parse_environment, ast::{Environment, BruFile}};
+use crate::dotenv::parse_dotenv;
+use crate::dynamic::DynamicVars;
+use crate::escape::{unquote_raw_placeholders, Context, RAW_PREFIX};
+use crate::redact::Redaction;
+use crate::template::{expand, Resolution};
+use crate::parser::{parse_environment, ast::{BruFile, Environment, Location, Part}};
+use crate::resolver::Resolver;
 
-fn find_collection_root(bru_file_path: &Path) -> Result<PathBuf, String> {
+pub const PROCESS_ENV_PREFIX: &str = "process.env.";
+
+pub fn find_collection_root(bru_file_path: &Path) -> Result<PathBuf, String> {
     let mut current = bru_file_path.parent();
 
     while let Some(dir) = current {
@@ -20,9 +28,13 @@ fn find_collection_root(bru_file_path: &Path) -> Result<PathBuf, String> {
         .ok_or_else(|| "Cannot determine collection root".to_string())
 }
 
-pub fn load_environment(bru_file_path: &Path, env_name: &str) -> Result<Environment, String> {
+pub fn environment_path(bru_file_path: &Path, env_name: &str) -> Result<PathBuf, String> {
     let collection_root = find_collection_root(bru_file_path)?;
-    let env_path = collection_root.join("environments").join(format!("{}.bru", env_name));
+    Ok(collection_root.join("environments").join(format!("{}.bru", env_name)))
+}
+
+pub fn load_environment(bru_file_path: &Path, env_name: &str) -> Result<Environment, String> {
+    let env_path = environment_path(bru_file_path, env_name)?;
 
     if !env_path.exists() {
         return Err(format!("Environment file not found: {:?}", env_path));
@@ -34,33 +46,216 @@ pub fn load_environment(bru_file_path: &Path, env_name: &str) -> Result<Environm
     parse_environment(&content)
 }
 
-pub fn apply_environment(bru: &mut BruFile, env: &Environment) {
-    bru.request.url = substitute_variables(&bru.request.url, &env.vars);
+/// Loads a global environment shared across collections. `spec` is either a
+/// path to a `.bru` file or a name looked up in `~/.config/bruq/globals/`
+/// (`$XDG_CONFIG_HOME/bruq/globals/` when set).
+pub fn load_global_environment(spec: &str) -> Result<Environment, String> {
+    let env_path = global_environment_path(spec)?;
 
-    if let Some(ref mut body) = bru.body {
-        body.content = substitute_variables(&body.content, &env.vars);
+    if !env_path.exists() {
+        return Err(format!("Global environment file not found: {:?}", env_path));
     }
 
-    for value in bru.headers.values_mut() {
-        *value = substitute_variables(value, &env.vars);
+    let content = fs::read_to_string(&env_path)
+        .map_err(|e| format!("Cannot read global environment file: {}", e))?;
+
+    parse_environment(&content)
+}
+
+pub fn global_environment_path(spec: &str) -> Result<PathBuf, String> {
+    let as_path = Path::new(spec);
+    if spec.ends_with(".bru") || as_path.components().count() > 1 {
+        return Ok(as_path.to_path_buf());
     }
+
+    let config_dir = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => std::env::var_os("HOME")
+            .map(|home| PathBuf::from(home).join(".config"))
+            .ok_or("Cannot locate the config directory: HOME is not set")?,
+    };
+
+    Ok(config_dir.join("bruq").join("globals").join(format!("{}.bru", spec)))
 }
 
-fn substitute_variables(text: &str, vars: &HashMap<String, String>) -> String {
-    let mut result = text.to_string();
+/// Builds the `process.env` view: the real process environment overlaid with
+/// the `.env` file at the collection root, as Bruno does.
+pub fn load_process_env(collection_root: &Path) -> Result<HashMap<String, String>, String> {
+    let mut process_env: HashMap<String, String> = std::env::vars().collect();
 
-    for (key, value) in vars {
-        let pattern = format!("{{{{{}}}}}", key);
-        result = result.replace(&pattern, value);
+    let dotenv_path = collection_root.join(".env");
+    if dotenv_path.exists() {
+        let content = fs::read_to_string(&dotenv_path)
+            .map_err(|e| format!("Cannot read .env file: {}", e))?;
+        process_env.extend(parse_dotenv(&content));
     }
 
-    result
+    Ok(process_env)
+}
+
+/// A placeholder that no variable source could resolve, and where it appears.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Unresolved {
+    pub name: String,
+    pub location: String,
+}
+
+impl std::fmt::Display for Unresolved {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{{{{{}}}}} in {}", self.name, self.location)
+    }
+}
+
+/// What `apply_environment` found while substituting.
+#[derive(Debug, Default)]
+pub struct Substituted {
+    pub unresolved: Vec<Unresolved>,
+    /// Where a secret was replaced by its `{{placeholder}}`, for `Redaction::apply`.
+    pub kept: Vec<Location>,
+}
+
+/// Substitutes variables in every part of the request through
+/// `BruFile::visit_text_mut`, so newly modelled blocks are covered too.
+pub fn apply_environment(
+    bru: &mut BruFile,
+    resolver: &Resolver,
+    dynamic: &mut DynamicVars,
+    redaction: Option<&Redaction>,
+) -> Result<Substituted, String> {
+    let mut unresolved = Vec::new();
+    let mut kept = Vec::new();
+    let mut substitution =
+        Substitution { resolver, dynamic, redaction, unresolved: &mut unresolved, placeholder_kept: false };
+
+    bru.visit_text_mut(&mut |location: &Location, text: &mut String| {
+        let context = Context::for_location(location);
+
+        if location.part != Part::Body {
+            *text = substitution.substitute(text, context, &location.to_string())?;
+            if substitution.placeholder_kept {
+                kept.push(location.clone());
+            }
+            return Ok(());
+        }
+
+        if context == Context::Json {
+            *text = unquote_raw_placeholders(text);
+        }
+
+        let mut lines = Vec::new();
+        let mut any_kept = false;
+        for (index, line) in text.split('\n').enumerate() {
+            let line_location = format!("body line {}", index + 1);
+            lines.push(substitution.substitute(line, context, &line_location)?);
+            any_kept |= substitution.placeholder_kept;
+        }
+        *text = lines.join("\n");
+        if any_kept {
+            kept.push(location.clone());
+        }
+        Ok::<(), String>(())
+    })?;
+
+    unresolved.dedup();
+    Ok(Substituted { unresolved, kept })
+}
+
+/// Substitutes variables in a setting outside the request model, such as
+/// the collection proxy, without escaping.
+pub fn substitute_text(
+    text: &mut String,
+    location: &str,
+    resolver: &Resolver,
+    dynamic: &mut DynamicVars,
+    redaction: Option<&Redaction>,
+    unresolved: &mut Vec<Unresolved>,
+) -> Result<(), String> {
+    let mut substitution = Substitution { resolver, dynamic, redaction, unresolved, placeholder_kept: false };
+    *text = substitution.substitute(text, Context::Plain, location)?;
+    Ok(())
+}
+
+/// Resolves a single `{{name}}` the way `apply_environment` would, without
+/// escaping. Returns `None` when nothing defines the name.
+pub fn resolve_value(
+    name: &str,
+    resolver: &Resolver,
+    dynamic: &mut DynamicVars,
+    redaction: Option<&Redaction>,
+) -> Result<Option<String>, String> {
+    let mut unresolved = Vec::new();
+    let mut substitution =
+        Substitution { resolver, dynamic, redaction, unresolved: &mut unresolved, placeholder_kept: false };
+    let value = substitution.substitute(&format!("{{{{{}}}}}", name), Context::Plain, "explain")?;
+
+    if unresolved.iter().any(|item| item.name == name) {
+        Ok(None)
+    } else {
+        Ok(Some(value))
+    }
+}
+
+struct Substitution<'a> {
+    resolver: &'a Resolver,
+    dynamic: &'a mut DynamicVars,
+    redaction: Option<&'a Redaction>,
+    unresolved: &'a mut Vec<Unresolved>,
+    /// Whether the last `substitute` call left a secret as its placeholder.
+    placeholder_kept: bool,
+}
+
+impl Substitution<'_> {
+    fn substitute(&mut self, text: &str, context: Context, location: &str) -> Result<String, String> {
+        let Substitution { resolver, dynamic, redaction, unresolved, placeholder_kept } = self;
+        *placeholder_kept = false;
+        let is_redacted = |name: &str| {
+            let name = name.strip_prefix(PROCESS_ENV_PREFIX).unwrap_or(name);
+            redaction.is_some_and(|r| !name.starts_with('$') && r.is_secret(name))
+        };
+
+        let resolve = &mut |name: &str| {
+            let name = name.strip_prefix(RAW_PREFIX).map(str::trim_start).unwrap_or(name);
+            if is_redacted(name) && (name.starts_with(PROCESS_ENV_PREFIX) || resolver.get(name).is_some()) {
+                *placeholder_kept = true;
+                return redaction.map(|r| Resolution::Literal(r.replacement(name)));
+            }
+
+            let resolution = if let Some(dynamic_name) = name.strip_prefix('$') {
+                dynamic.resolve(dynamic_name).map(Resolution::Literal)
+            } else if let Some(env_name) = name.strip_prefix(PROCESS_ENV_PREFIX) {
+                resolver.process_env(env_name).map(|v| Resolution::Literal(v.to_string()))
+            } else {
+                resolver.get(name).map(|v| Resolution::Template(v.to_string()))
+            };
+
+            if resolution.is_none() {
+                unresolved.push(Unresolved {
+                    name: name.to_string(),
+                    location: location.to_string(),
+                });
+            }
+            resolution
+        };
+
+        let result = expand(text, resolve, &mut |preceding, name, value| {
+            let bare = name.strip_prefix(RAW_PREFIX).map(str::trim_start).unwrap_or(name);
+            if name.starts_with(RAW_PREFIX) || is_redacted(bare) {
+                value
+            } else {
+                context.escape(preceding, &value)
+            }
+        })?;
+        Ok(result)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::parser::ast::{Body, Method, Request};
+    use crate::parser::ast::{Auth, Body, Method, Request};
+    use crate::redact::RedactMode;
+    use crate::resolver::Source;
+    use std::collections::HashSet;
     use tempfile::TempDir;
 
     fn create_bruno_collection(temp: &TempDir, nested_path: &str) -> PathBuf {
@@ -133,23 +328,56 @@ mod tests {
         assert!(result.unwrap_err().contains("Environment file not found"));
     }
 
-    fn make_bru(method: Method, url: &str, body: Option<Body>, headers: HashMap<String, String>) -> BruFile {
+    #[test]
+    fn load_global_environment_from_path() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("Shared.bru");
+        fs::write(&path, "vars {\n  SHARED_TOKEN: abc\n}\n").unwrap();
+
+        let env = load_global_environment(path.to_str().unwrap()).unwrap();
+        assert_eq!(env.vars.get("SHARED_TOKEN").unwrap(), "abc");
+    }
+
+    #[test]
+    fn global_environment_name_resolves_to_config_dir() {
+        let path = global_environment_path("Shared").unwrap();
+        assert!(path.ends_with("bruq/globals/Shared.bru"));
+
+        assert_eq!(global_environment_path("./team.bru").unwrap(), PathBuf::from("./team.bru"));
+    }
+
+    #[test]
+    fn load_global_environment_not_found() {
+        let temp = TempDir::new().unwrap();
+        let missing = temp.path().join("Missing.bru");
+        let result = load_global_environment(missing.to_str().unwrap());
+        assert!(result.unwrap_err().contains("Global environment file not found"));
+    }
+
+    fn make_bru(method: Method, url: &str, body: Option<Body>, headers: Vec<(String, String)>) -> BruFile {
         BruFile {
             request: Request { method, url: url.to_string() },
             body,
             headers,
+            ..Default::default()
         }
     }
 
+    fn env_resolver(vars: HashMap<String, String>) -> Resolver {
+        let mut resolver = Resolver::default();
+        resolver.push(Source::Environment("Test".to_string()), vars);
+        resolver
+    }
+
     #[test]
     fn apply_environment_substitutes_url() {
         let mut vars = HashMap::new();
         vars.insert("HOST".to_string(), "api.example.com".to_string());
-        let env = Environment { vars };
+        let env = env_resolver(vars);
 
-        let mut bru = make_bru(Method::Get, "https://{{HOST}}/users", None, HashMap::new());
+        let mut bru = make_bru(Method::Get, "https://{{HOST}}/users", None, Vec::new());
 
-        apply_environment(&mut bru, &env);
+        apply_environment(&mut bru, &env, &mut DynamicVars::new(None), None).unwrap();
         assert_eq!(bru.request.url, "https://api.example.com/users");
     }
 
@@ -157,29 +385,223 @@ mod tests {
     fn apply_environment_substitutes_body() {
         let mut vars = HashMap::new();
         vars.insert("TOKEN".to_string(), "secret123".to_string());
-        let env = Environment { vars };
+        let env = env_resolver(vars);
 
         let body = Body {
             body_type: "json".to_string(),
             content: r#"{"token": "{{TOKEN}}"}"#.to_string(),
+            ..Default::default()
         };
-        let mut bru = make_bru(Method::Post, "https://api.example.com", Some(body), HashMap::new());
+        let mut bru = make_bru(Method::Post, "https://api.example.com", Some(body), Vec::new());
 
-        apply_environment(&mut bru, &env);
+        apply_environment(&mut bru, &env, &mut DynamicVars::new(None), None).unwrap();
         assert_eq!(bru.body.unwrap().content, r#"{"token": "secret123"}"#);
     }
 
+    #[test]
+    fn load_process_env_reads_dotenv() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join(".env"), "BRUQ_TEST_DOTENV_KEY=from-dotenv\n").unwrap();
+
+        let process_env = load_process_env(temp.path()).unwrap();
+        assert_eq!(process_env.get("BRUQ_TEST_DOTENV_KEY").unwrap(), "from-dotenv");
+        assert!(process_env.contains_key("PATH"));
+    }
+
+    #[test]
+    fn apply_environment_substitutes_process_env() {
+        let mut process_env = HashMap::new();
+        process_env.insert("API_KEY".to_string(), "env-key".to_string());
+        let mut env = Resolver::default();
+        env.push(Source::ProcessEnv, process_env);
+
+        let headers = vec![("X-Api-Key".to_string(), "{{process.env.API_KEY}}".to_string())];
+        let mut bru = make_bru(Method::Get, "https://api.example.com?k={{process.env.API_KEY}}", None, headers);
+
+        apply_environment(&mut bru, &env, &mut DynamicVars::new(None), None).unwrap();
+        assert_eq!(bru.request.url, "https://api.example.com?k=env-key");
+        assert_eq!(bru.header("X-Api-Key").unwrap(), "env-key");
+    }
+
+    #[test]
+    fn apply_environment_evaluates_dynamic_vars_per_occurrence() {
+        let body = Body {
+            body_type: "json".to_string(),
+            content: r#"{"a": "{{$guid}}", "b": "{{$guid}}", "c": "{{$unknown}}"}"#.to_string(),
+            ..Default::default()
+        };
+        let mut bru = make_bru(Method::Post, "https://api.example.com?t={{$timestamp}}", Some(body), Vec::new());
+
+        apply_environment(&mut bru, &Resolver::default(), &mut DynamicVars::new(Some(1)), None).unwrap();
+        assert_eq!(bru.request.url, "https://api.example.com?t=1704067200");
+
+        let content = bru.body.unwrap().content;
+        let guids: Vec<&str> = content.split('"').filter(|s| s.len() == 36).collect();
+        assert_eq!(guids.len(), 2);
+        assert_ne!(guids[0], guids[1]);
+        assert!(content.contains("{{$unknown}}"));
+    }
+
+    #[test]
+    fn apply_environment_resolves_nested_vars_regardless_of_order() {
+        let mut vars = HashMap::new();
+        vars.insert("BASE_URL".to_string(), "{{HOST}}:{{PORT}}".to_string());
+        vars.insert("HOST".to_string(), "http://localhost".to_string());
+        vars.insert("PORT".to_string(), "3000".to_string());
+        let env = env_resolver(vars);
+
+        let mut bru = make_bru(Method::Get, "{{ BASE_URL }}/users", None, Vec::new());
+
+        apply_environment(&mut bru, &env, &mut DynamicVars::new(None), None).unwrap();
+        assert_eq!(bru.request.url, "http://localhost:3000/users");
+    }
+
+    #[test]
+    fn apply_environment_reports_cycles() {
+        let mut vars = HashMap::new();
+        vars.insert("A".to_string(), "{{B}}".to_string());
+        vars.insert("B".to_string(), "{{A}}".to_string());
+        let env = env_resolver(vars);
+
+        let mut bru = make_bru(Method::Get, "{{A}}", None, Vec::new());
+
+        let err = apply_environment(&mut bru, &env, &mut DynamicVars::new(None), None).unwrap_err();
+        assert!(err.contains("cycle"));
+    }
+
+    #[test]
+    fn apply_environment_collects_unresolved_placeholders() {
+        let body = Body {
+            body_type: "json".to_string(),
+            content: "{\n  \"id\": \"{{USER_ID}}\"\n}".to_string(),
+            ..Default::default()
+        };
+        let headers = vec![("Authorization".to_string(), "Bearer {{TOKEN}}".to_string())];
+        let mut bru = make_bru(Method::Post, "{{HOST}}/users", Some(body), headers);
+
+        let unresolved = apply_environment(&mut bru, &Resolver::default(), &mut DynamicVars::new(None), None).unwrap().unresolved;
+        let reported: Vec<String> = unresolved.iter().map(|u| u.to_string()).collect();
+        assert_eq!(
+            reported,
+            vec!["{{HOST}} in url", "{{TOKEN}} in header Authorization", "{{USER_ID}} in body line 2"]
+        );
+        assert_eq!(bru.request.url, "{{HOST}}/users");
+    }
+
     #[test]
     fn apply_environment_substitutes_headers() {
         let mut vars = HashMap::new();
         vars.insert("API_KEY".to_string(), "key123".to_string());
-        let env = Environment { vars };
+        let env = env_resolver(vars);
 
-        let mut headers = HashMap::new();
-        headers.insert("Authorization".to_string(), "Bearer {{API_KEY}}".to_string());
+        let headers = vec![("Authorization".to_string(), "Bearer {{API_KEY}}".to_string())];
         let mut bru = make_bru(Method::Get, "https://api.example.com", None, headers);
 
-        apply_environment(&mut bru, &env);
-        assert_eq!(bru.headers.get("Authorization").unwrap(), "Bearer key123");
+        apply_environment(&mut bru, &env, &mut DynamicVars::new(None), None).unwrap();
+        assert_eq!(bru.header("Authorization").unwrap(), "Bearer key123");
+    }
+
+    #[test]
+    fn apply_environment_substitutes_every_part() {
+        let vars = [
+            ("HEADER", "X-Tenant"),
+            ("TENANT", "acme"),
+            ("ID", "42"),
+            ("PAGE", "2"),
+            ("USER", "bob"),
+            ("PASS", "pw"),
+            ("NAME", "avatar"),
+            ("DIR", "/tmp"),
+            ("TIMEOUT", "5000"),
+        ];
+        let env = env_resolver(vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect());
+
+        let headers = vec![("{{HEADER}}".to_string(), "{{TENANT}}".to_string())];
+        let body = Body {
+            body_type: "multipart-form".to_string(),
+            content: String::new(),
+            fields: vec![("{{NAME}}".to_string(), "@file({{DIR}}/a.png)".to_string())],
+        };
+        let mut bru = make_bru(Method::Post, "https://api.example.com/users/:id", Some(body), headers);
+        bru.path_params = vec![("id".to_string(), "{{ID}}".to_string())];
+        bru.query = vec![("page".to_string(), "{{PAGE}}".to_string())];
+        bru.auth = Some(Auth::Basic { username: "{{USER}}".to_string(), password: "{{PASS}}".to_string() });
+        bru.settings.insert("timeout".to_string(), "{{TIMEOUT}}".to_string());
+
+        let unresolved = apply_environment(&mut bru, &env, &mut DynamicVars::new(None), None).unwrap().unresolved;
+        assert!(unresolved.is_empty());
+
+        assert_eq!(bru.header("X-Tenant").unwrap(), "acme");
+        assert_eq!(bru.path_params[0].1, "42");
+        assert_eq!(bru.query[0].1, "2");
+        assert!(matches!(bru.auth, Some(Auth::Basic { ref username, ref password }) if username == "bob" && password == "pw"));
+        assert_eq!(bru.body.unwrap().fields[0], ("avatar".to_string(), "@file(/tmp/a.png)".to_string()));
+        assert_eq!(bru.settings.get("timeout").unwrap(), "5000");
+    }
+
+    #[test]
+    fn apply_environment_escapes_for_the_placement_context() {
+        let vars = [
+            ("BASE_URL", "https://api.example.com"),
+            ("QUERY", "a&b c"),
+            ("MESSAGE", "line \"one\"\nline two"),
+            ("AGE", "42"),
+            ("META", r#"{"admin": true}"#),
+            ("TENANT", "acme\r\nX-Injected: 1"),
+        ];
+        let env = env_resolver(vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect());
+
+        let body = Body {
+            body_type: "json".to_string(),
+            content: r#"{"msg": "{{MESSAGE}}", "age": "{{raw:AGE}}", "meta": {{META}}}"#.to_string(),
+            ..Default::default()
+        };
+        let headers = vec![("X-Tenant".to_string(), "{{TENANT}}".to_string())];
+        let mut bru = make_bru(Method::Post, "{{BASE_URL}}/search?q={{QUERY}}", Some(body), headers);
+
+        apply_environment(&mut bru, &env, &mut DynamicVars::new(None), None).unwrap();
+
+        assert_eq!(bru.request.url, "https://api.example.com/search?q=a%26b%20c");
+        assert_eq!(bru.header("X-Tenant").unwrap(), "acme X-Injected: 1");
+        assert_eq!(
+            bru.body.unwrap().content,
+            r#"{"msg": "line \"one\"\nline two", "age": 42, "meta": {"admin": true}}"#
+        );
+    }
+
+    #[test]
+    fn apply_environment_redacts_secrets() {
+        let vars = [
+            ("BASE_URL", "https://api.example.com"),
+            ("API_TOKEN", "live-token"),
+            ("DB_CONN", "postgres://u:p@db"),
+            ("AUTH", "Token {{API_TOKEN}}"),
+        ];
+        let env = env_resolver(vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect());
+        let headers = vec![
+            ("X-Auth".to_string(), "{{AUTH}}".to_string()),
+            ("Authorization".to_string(), "Bearer {{API_TOKEN}}".to_string()),
+        ];
+        let body = Body {
+            body_type: "json".to_string(),
+            content: r#"{"conn": "{{DB_CONN}}"}"#.to_string(),
+            ..Default::default()
+        };
+
+        let masked = Redaction::new(RedactMode::Mask, HashSet::from(["DB_CONN".to_string()]), &[]);
+        let mut bru = make_bru(Method::Post, "{{BASE_URL}}/q?token={{API_TOKEN}}", Some(body.clone()), headers.clone());
+        let unresolved = apply_environment(&mut bru, &env, &mut DynamicVars::new(None), Some(&masked)).unwrap().unresolved;
+        assert!(unresolved.is_empty());
+        assert_eq!(bru.request.url, "https://api.example.com/q?token=***");
+        assert_eq!(bru.header("X-Auth").unwrap(), "Token ***");
+        assert_eq!(bru.body.unwrap().content, r#"{"conn": "***"}"#);
+
+        let placeholder = Redaction::new(RedactMode::Placeholder, HashSet::from(["DB_CONN".to_string()]), &[]);
+        let mut bru = make_bru(Method::Post, "{{BASE_URL}}/q?token={{API_TOKEN}}", Some(body), headers);
+        let kept = apply_environment(&mut bru, &env, &mut DynamicVars::new(None), Some(&placeholder)).unwrap().kept;
+        placeholder.apply(&mut bru, &kept);
+        assert_eq!(bru.request.url, "https://api.example.com/q?token={{API_TOKEN}}");
+        assert_eq!(bru.header("Authorization").unwrap(), "Bearer {{API_TOKEN}}");
+        assert_eq!(bru.body.unwrap().content, r#"{"conn": "{{DB_CONN}}"}"#);
     }
 }
@@ -0,0 +1,203 @@
+use crate::parser::ast::{Location, Part};
+
+/// Placeholders written as `{{raw:NAME}}` are inserted verbatim. Inside a JSON
+/// body, `"{{raw:NAME}}"` also drops the quotes so numbers, booleans and
+/// objects keep their type.
+pub const RAW_PREFIX: &str = "raw:";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Context {
+    Json,
+    Url,
+    PathSegment,
+    Header,
+    Plain,
+}
+
+impl Context {
+    pub fn for_location(location: &Location) -> Context {
+        match location.part {
+            Part::Url => Context::Url,
+            Part::PathParam if !location.is_name => Context::PathSegment,
+            Part::Header => Context::Header,
+            Part::Body if location.key.as_deref() == Some("json") => Context::Json,
+            _ => Context::Plain,
+        }
+    }
+
+    /// Escapes `value` for insertion right after `preceding`, the text
+    /// rendered so far.
+    pub fn escape(self, preceding: &str, value: &str) -> String {
+        match self {
+            Context::Json if inside_json_string(preceding) => escape_json_string(value),
+            Context::Url => match url_component(preceding) {
+                UrlComponent::Base => value.to_string(),
+                // A `?` in a path value starts the query, as it would if written into the URL.
+                UrlComponent::Path | UrlComponent::Query => percent_encode(value, QUERY_SAFE),
+                UrlComponent::QueryValue => percent_encode(value, ""),
+            },
+            Context::PathSegment => percent_encode(value, SEGMENT_SAFE),
+            Context::Header => value.replace("\r\n", " ").replace(['\r', '\n'], " "),
+            Context::Json | Context::Plain => value.to_string(),
+        }
+    }
+}
+
+const SEGMENT_SAFE: &str = "!$&'()*+,;=:@%";
+const QUERY_SAFE: &str = "!$&'()*+,;=:@%/?";
+
+#[derive(Debug, PartialEq)]
+enum UrlComponent {
+    Base,
+    Path,
+    Query,
+    QueryValue,
+}
+
+fn url_component(preceding: &str) -> UrlComponent {
+    if let Some(pos) = preceding.find('?') {
+        let last_pair = preceding[pos + 1..].rsplit('&').next().unwrap_or_default();
+        return if last_pair.contains('=') {
+            UrlComponent::QueryValue
+        } else {
+            UrlComponent::Query
+        };
+    }
+
+    let after_scheme = match preceding.find("://") {
+        Some(pos) => &preceding[pos + 3..],
+        None => preceding,
+    };
+
+    if after_scheme.contains('/') {
+        UrlComponent::Path
+    } else {
+        UrlComponent::Base
+    }
+}
+
+/// Values that are already percent-encoded keep their `%XX` escapes, so
+/// `a%20b` is not turned into `a%2520b`.
+fn percent_encode(value: &str, safe: &str) -> String {
+    let mut result = String::new();
+    for (i, c) in value.char_indices() {
+        let is_escape = c == '%' && value.get(i + 1..i + 3).is_some_and(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()));
+        if is_escape || c.is_ascii_alphanumeric() || "-._~".contains(c) || safe.contains(c) {
+            result.push(c);
+        } else {
+            let mut buf = [0u8; 4];
+            for byte in c.encode_utf8(&mut buf).bytes() {
+                result.push_str(&format!("%{:02X}", byte));
+            }
+        }
+    }
+    result
+}
+
+fn inside_json_string(preceding: &str) -> bool {
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for c in preceding.chars() {
+        if escaped {
+            escaped = false;
+        } else if in_string && c == '\\' {
+            escaped = true;
+        } else if c == '"' {
+            in_string = !in_string;
+        }
+    }
+
+    in_string
+}
+
+fn escape_json_string(value: &str) -> String {
+    let mut result = String::new();
+    for c in value.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// Turns `"{{raw:NAME}}"` into `{{raw:NAME}}` so the value lands as a typed
+/// JSON value instead of a string.
+pub fn unquote_raw_placeholders(json: &str) -> String {
+    let mut result = String::new();
+    let mut rest = json;
+
+    while let Some(start) = rest.find("\"{{") {
+        let inner_start = start + 1;
+        let Some(close) = rest[inner_start..].find("}}") else {
+            break;
+        };
+        let inner_end = inner_start + close + 2;
+        let name = rest[inner_start + 2..inner_end - 2].trim();
+
+        if name.starts_with(RAW_PREFIX) && rest[inner_end..].starts_with('"') {
+            result.push_str(&rest[..start]);
+            result.push_str(&rest[inner_start..inner_end]);
+            rest = &rest[inner_end + 1..];
+        } else {
+            result.push_str(&rest[..inner_start]);
+            rest = &rest[inner_start..];
+        }
+    }
+
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_values_escaped_only_inside_strings() {
+        let value = "say \"hi\"\nbye\\";
+        assert_eq!(Context::Json.escape(r#"{"msg": ""#, value), r#"say \"hi\"\nbye\\"#);
+        assert_eq!(Context::Json.escape(r#"{"count": "#, "42"), "42");
+        assert_eq!(Context::Json.escape(r#"{"a": "x\"y", "b": "#, "{}"), "{}");
+    }
+
+    #[test]
+    fn url_components() {
+        assert_eq!(Context::Url.escape("", "http://host:3000/api"), "http://host:3000/api");
+        assert_eq!(Context::Url.escape("https://", "api.example.com"), "api.example.com");
+        assert_eq!(Context::Url.escape("http://h/users/", "a b/c"), "a%20b/c");
+        assert_eq!(Context::Url.escape("http://h/search?q=", "a&b=c d"), "a%26b%3Dc%20d");
+        assert_eq!(Context::Url.escape("http://h/search?", "a=1&b=2"), "a=1&b=2");
+        assert_eq!(Context::Url.escape("http://h/search?a=1&b=x", "y z"), "y%20z");
+        assert_eq!(Context::Url.escape("http://h/", "é"), "%C3%A9");
+        assert_eq!(Context::Url.escape("http://h/", "users?page=2&q=a b"), "users?page=2&q=a%20b");
+        assert_eq!(Context::Url.escape("http://h/search?q=", "a%20b"), "a%20b");
+        assert_eq!(Context::Url.escape("http://h/search?q=", "100%"), "100%25");
+        assert_eq!(Context::Url.escape("http://h/search?q=", "%zz"), "%25zz");
+    }
+
+    #[test]
+    fn header_values_cannot_break_lines() {
+        assert_eq!(Context::Header.escape("", "a\r\nX-Evil: 1"), "a X-Evil: 1");
+    }
+
+    #[test]
+    fn path_segments_encode_slashes() {
+        assert_eq!(Context::PathSegment.escape("", "a/b c"), "a%2Fb%20c");
+    }
+
+    #[test]
+    fn raw_placeholders_lose_their_quotes() {
+        let json = r#"{"age": "{{raw:AGE}}", "name": "{{NAME}}", "meta": "{{ raw:META }}"}"#;
+        assert_eq!(
+            unquote_raw_placeholders(json),
+            r#"{"age": {{raw:AGE}}, "name": "{{NAME}}", "meta": {{ raw:META }}}"#
+        );
+    }
+}
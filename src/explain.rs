@@ -0,0 +1,219 @@
+use std::fs;
+use std::path::Path;
+
+use crate::dynamic::DynamicVars;
+use crate::environment::{resolve_value, PROCESS_ENV_PREFIX};
+use crate::escape::RAW_PREFIX;
+use crate::parser::ast::BruFile;
+use crate::redact::{Redaction, MASK};
+use crate::resolver::{Layer, Resolver};
+use crate::template::next_placeholder;
+
+/// Describes every placeholder the request uses: its resolved value, the
+/// layer that supplied it and the layers it shadows. Variables referenced
+/// from other variables' values are listed after the ones that use them.
+pub fn explain(
+    bru: &BruFile,
+    resolver: &Resolver,
+    dynamic: &mut DynamicVars,
+    redaction: &Redaction,
+) -> Result<String, String> {
+    let mut output = String::new();
+
+    for name in used_variables(bru, resolver) {
+        let value = resolve_value(&name, resolver, dynamic, Some(redaction))?;
+        match value {
+            Some(value) => output.push_str(&format!("{{{{{}}}}} = {}\n", name, value)),
+            None => {
+                output.push_str(&format!("{{{{{}}}}} is unresolved\n", name));
+                continue;
+            }
+        }
+
+        if name.starts_with('$') {
+            output.push_str("  from dynamic variable\n");
+        } else if name.starts_with(PROCESS_ENV_PREFIX) {
+            output.push_str("  from process.env\n");
+        } else {
+            let definitions = resolver.definitions(&name);
+            for (index, layer) in definitions.iter().enumerate() {
+                if index == 0 {
+                    output.push_str(&format!("  from {}\n", describe_layer(layer, &name)));
+                } else {
+                    let value = if redaction.is_secret(&name) { MASK } else { &layer.vars[&name] };
+                    output.push_str(&format!("  shadows {} = {}\n", describe_layer(layer, &name), value));
+                }
+            }
+        }
+    }
+
+    Ok(output)
+}
+
+/// Placeholder names in the order they first appear, followed by the names
+/// their variable values refer to.
+fn used_variables(bru: &BruFile, resolver: &Resolver) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut bru = bru.clone();
+    let _ = bru.visit_text_mut(&mut |_, text| {
+        collect_placeholders(text, &mut names);
+        Ok::<(), String>(())
+    });
+
+    let mut index = 0;
+    while index < names.len() {
+        if let Some(value) = resolver.get(&names[index]) {
+            collect_placeholders(value, &mut names);
+        }
+        index += 1;
+    }
+
+    names
+}
+
+fn collect_placeholders(text: &str, names: &mut Vec<String>) {
+    let mut rest = text;
+    while let Some((_, end, name)) = next_placeholder(rest) {
+        let name = name.strip_prefix(RAW_PREFIX).map(str::trim_start).unwrap_or(name);
+        if !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+        rest = &rest[end..];
+    }
+}
+
+fn describe_layer(layer: &Layer, name: &str) -> String {
+    match &layer.file {
+        Some(file) => match definition_line(file, name) {
+            Some(line) => format!("{} ({}:{})", layer.source, file.display(), line),
+            None => format!("{} ({})", layer.source, file.display()),
+        },
+        None => layer.source.to_string(),
+    }
+}
+
+/// The 1-based line defining `name`: inside a `vars` block (or a
+/// `vars:secret` list) for `.bru` files, a `KEY=VALUE` line otherwise.
+fn definition_line(path: &Path, name: &str) -> Option<usize> {
+    let content = fs::read_to_string(path).ok()?;
+    let is_bru = path.extension().is_some_and(|ext| ext == "bru");
+    let mut in_vars = false;
+
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+
+        if is_bru {
+            if trimmed.starts_with("vars") && (trimmed.ends_with('{') || trimmed.ends_with('[')) {
+                in_vars = true;
+                continue;
+            }
+            if trimmed == "}" || trimmed == "]" {
+                in_vars = false;
+                continue;
+            }
+            if in_vars && defines(trimmed.trim_end_matches(','), name, ':') {
+                return Some(index + 1);
+            }
+        } else {
+            let trimmed = trimmed.strip_prefix("export ").unwrap_or(trimmed);
+            if defines(trimmed, name, '=') {
+                return Some(index + 1);
+            }
+        }
+    }
+
+    None
+}
+
+fn defines(line: &str, name: &str, separator: char) -> bool {
+    match line.strip_prefix(name) {
+        Some(rest) => rest.is_empty() || rest.trim_start().starts_with(separator),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::redact::RedactMode;
+    use crate::resolver::Source;
+    use std::collections::{HashMap, HashSet};
+    use tempfile::TempDir;
+
+    fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn reports_source_line_and_shadowed_layers() {
+        let temp = TempDir::new().unwrap();
+        let env_file = temp.path().join("Local.bru");
+        fs::write(&env_file, "vars {\n  HOST: localhost\n  BASE_URL: http://{{HOST}}\n}\n").unwrap();
+
+        let mut resolver = Resolver::default();
+        resolver.push(Source::Collection, vars(&[("HOST", "collection-host")]));
+        resolver.push_file(
+            Source::Environment("Local".into()),
+            vars(&[("HOST", "localhost"), ("BASE_URL", "http://{{HOST}}")]),
+            env_file.clone(),
+        );
+
+        let mut bru = BruFile::default();
+        bru.request.url = "{{BASE_URL}}/users/{{ID}}".to_string();
+        let redaction = Redaction::new(RedactMode::Mask, HashSet::new(), &[]);
+
+        let output = explain(&bru, &resolver, &mut DynamicVars::new(None), &redaction).unwrap();
+        let expected = format!(
+            "{{{{BASE_URL}}}} = http://collection-host\n  from environment Local ({}:3)\n\
+             {{{{ID}}}} is unresolved\n\
+             {{{{HOST}}}} = collection-host\n  from collection vars\n  shadows environment Local ({}:2) = localhost\n",
+            env_file.display(),
+            env_file.display()
+        );
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn masks_secrets_and_labels_dynamic_values() {
+        let mut resolver = Resolver::default();
+        resolver.push(Source::Cli, vars(&[("API_TOKEN", "live")]));
+        resolver.push(Source::Request, vars(&[("API_TOKEN", "stale")]));
+
+        let mut bru = BruFile::default();
+        bru.request.url = "http://h/?t={{API_TOKEN}}&id={{$randomInt}}".to_string();
+        let redaction = Redaction::new(RedactMode::Mask, HashSet::new(), &[]);
+
+        let output = explain(&bru, &resolver, &mut DynamicVars::new(Some(7)), &redaction).unwrap();
+        assert!(output.starts_with("{{API_TOKEN}} = ***\n  from --var\n  shadows request vars = ***\n"));
+        assert!(output.contains("  from dynamic variable\n"));
+        assert!(!output.contains("live"));
+    }
+
+    #[test]
+    fn names_the_secret_store() {
+        let mut resolver = Resolver::default();
+        resolver.push(Source::SecretStore("file .env.secrets".into()), vars(&[("KEY", "live")]));
+        resolver.push(Source::Environment("Local".into()), vars(&[("KEY", "")]));
+
+        let mut bru = BruFile::default();
+        bru.request.url = "http://h/?k={{KEY}}".to_string();
+        let redaction = Redaction::new(RedactMode::Mask, HashSet::from(["KEY".to_string()]), &[]);
+
+        let output = explain(&bru, &resolver, &mut DynamicVars::new(None), &redaction).unwrap();
+        assert_eq!(output, "{{KEY}} = ***\n  from secret store (file .env.secrets)\n  shadows environment Local = ***\n");
+    }
+
+    #[test]
+    fn finds_definition_lines() {
+        let temp = TempDir::new().unwrap();
+        let bru_file = temp.path().join("env.bru");
+        fs::write(&bru_file, "headers {\n  TOKEN: x\n}\nvars {\n  TOKEN_URL: a\n  TOKEN: b\n}\nvars:secret [\n  KEY\n]\n").unwrap();
+        let dotenv = temp.path().join("overrides.env");
+        fs::write(&dotenv, "# comment\nexport ID = 1\n").unwrap();
+
+        assert_eq!(definition_line(&bru_file, "TOKEN"), Some(6));
+        assert_eq!(definition_line(&bru_file, "KEY"), Some(9));
+        assert_eq!(definition_line(&dotenv, "ID"), Some(2));
+        assert_eq!(definition_line(&dotenv, "NOPE"), None);
+    }
+}
@@ -0,0 +1,50 @@
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star_p, star_t)) => {
+                    p = star_p + 1;
+                    t = star_t + 1;
+                    backtrack = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn literal_match() {
+        assert!(glob_match("node_modules", "node_modules"));
+        assert!(!glob_match("node_modules", "node_modules2"));
+    }
+
+    #[test]
+    fn star_and_question_mark() {
+        assert!(glob_match("*.example.com", "api.example.com"));
+        assert!(!glob_match("*.example.com", "example.org"));
+        assert!(glob_match("v?", "v1"));
+        assert!(glob_match("*TOKEN*", "GITHUB_TOKEN"));
+        assert!(glob_match("*", ""));
+    }
+}
@@ -0,0 +1,252 @@
+const INDENT: &str = "  ";
+
+/// Re-indents a JSON document with two spaces per level, keeping keys in
+/// their original order. Returns `None` when `json` is not valid JSON, so
+/// callers can fall back to the text as written.
+pub fn pretty_print(json: &str) -> Option<String> {
+    pretty_print_indented(json, INDENT)
+}
+
+/// Like [`pretty_print`], with `indent` for each level.
+pub fn pretty_print_indented(json: &str, indent: &str) -> Option<String> {
+    serde_json::from_str::<serde_json::Value>(json).ok()?;
+
+    let mut result = String::new();
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut chars = json.trim().chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            result.push(c);
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+
+        match c {
+            '"' => {
+                in_string = true;
+                result.push(c);
+            }
+            '{' | '[' => {
+                result.push(c);
+                while chars.next_if(|c| c.is_whitespace()).is_some() {}
+                if let Some(close) = chars.next_if(|&n| n == '}' || n == ']') {
+                    result.push(close);
+                } else {
+                    depth += 1;
+                    push_newline(&mut result, indent, depth);
+                }
+            }
+            '}' | ']' => {
+                depth -= 1;
+                push_newline(&mut result, indent, depth);
+                result.push(c);
+            }
+            ',' => {
+                result.push(c);
+                push_newline(&mut result, indent, depth);
+            }
+            ':' => result.push_str(": "),
+            c if c.is_whitespace() => {}
+            c => result.push(c),
+        }
+    }
+
+    Some(result)
+}
+
+/// Rebuilds a JSON document token by token, passing each string (unescaped)
+/// to `string` and each number, `true`, `false` and `null` to `literal`.
+/// Layout and key order are kept, which makes this the way to turn a JSON
+/// body into a literal in another language. Returns `None` for invalid JSON.
+pub fn rewrite_tokens<S, L>(json: &str, string: S, literal: L) -> Option<String>
+where
+    S: Fn(&str) -> String,
+    L: Fn(&str) -> String,
+{
+    serde_json::from_str::<serde_json::Value>(json).ok()?;
+
+    let text = json.trim();
+    let mut result = String::new();
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        if c == '"' {
+            let mut escaped = false;
+            let mut end = start;
+            for (i, c) in chars.by_ref() {
+                if escaped {
+                    escaped = false;
+                } else if c == '\\' {
+                    escaped = true;
+                } else if c == '"' {
+                    end = i;
+                    break;
+                }
+            }
+            result.push_str(&string(&serde_json::from_str::<String>(&text[start..=end]).ok()?));
+        } else if c.is_ascii_alphanumeric() || c == '-' {
+            let mut end = start + c.len_utf8();
+            while let Some((i, c)) = chars.next_if(|(_, c)| c.is_ascii_alphanumeric() || "+-.".contains(*c)) {
+                end = i + c.len_utf8();
+            }
+            result.push_str(&literal(&text[start..end]));
+        } else {
+            result.push(c);
+        }
+    }
+
+    Some(result)
+}
+
+/// Splits a JSON object into its top-level members, in their original order.
+/// Keys are unescaped; values stay JSON text with insignificant whitespace
+/// removed. Returns `None` unless `json` is a valid JSON object.
+pub fn object_members(json: &str) -> Option<Vec<(String, String)>> {
+    if !serde_json::from_str::<serde_json::Value>(json).ok()?.is_object() {
+        return None;
+    }
+
+    let compact = compact(json);
+    let inner = &compact[1..compact.len() - 1];
+    let mut members = Vec::new();
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut start = 0;
+
+    for (i, c) in inner.char_indices() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '{' | '[' => depth += 1,
+            '}' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                members.push(split_member(&inner[start..i])?);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if !inner.is_empty() {
+        members.push(split_member(&inner[start..])?);
+    }
+
+    Some(members)
+}
+
+/// Splits `"key":value` after the closing quote of the key.
+fn split_member(member: &str) -> Option<(String, String)> {
+    let mut escaped = false;
+    let end = member.char_indices().skip(1).find_map(|(i, c)| {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == '"' {
+            return Some(i);
+        }
+        None
+    })?;
+    let key = serde_json::from_str::<String>(&member[..=end]).ok()?;
+    let value = member[end + 1..].strip_prefix(':')?;
+    Some((key, value.to_string()))
+}
+
+/// Drops whitespace outside strings.
+fn compact(json: &str) -> String {
+    let mut result = String::new();
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for c in json.chars() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+        } else if c == '"' {
+            in_string = true;
+        } else if c.is_whitespace() {
+            continue;
+        }
+        result.push(c);
+    }
+
+    result
+}
+
+fn push_newline(result: &mut String, indent: &str, depth: usize) {
+    result.push('\n');
+    result.push_str(&indent.repeat(depth));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indents_and_keeps_key_order() {
+        let json = r#"{"z": 1, "a": [true, null, {"k": "v"}], "m": {}}"#;
+        assert_eq!(
+            pretty_print(json).unwrap(),
+            "{\n  \"z\": 1,\n  \"a\": [\n    true,\n    null,\n    {\n      \"k\": \"v\"\n    }\n  ],\n  \"m\": {}\n}"
+        );
+    }
+
+    #[test]
+    fn leaves_strings_alone() {
+        let json = r#"{"text":"a, b: {c} [d] \"e\"  f"}"#;
+        assert_eq!(pretty_print(json).unwrap(), "{\n  \"text\": \"a, b: {c} [d] \\\"e\\\"  f\"\n}");
+    }
+
+    #[test]
+    fn rejects_invalid_json() {
+        assert_eq!(pretty_print(r#"{"id": {{ID}}}"#), None);
+        assert_eq!(pretty_print("not json"), None);
+    }
+
+    #[test]
+    fn rewrites_strings_and_literals() {
+        let json = r#"{"b": [true, null, -1.5e3], "a": "x\"\u00e9"}"#;
+        let rewritten = rewrite_tokens(json, |s| format!("<{}>", s), |l| l.to_uppercase()).unwrap();
+        assert_eq!(rewritten, r#"{<b>: [TRUE, NULL, -1.5E3], <a>: <x"é>}"#);
+        assert_eq!(rewrite_tokens("{oops}", |s| s.to_string(), |l| l.to_string()), None);
+    }
+
+    #[test]
+    fn splits_object_members_in_order() {
+        let json = "{\n  \"z\": \"a, b\",\n  \"a\\\"b\": [1, {\"k\": \"v w\"}],\n  \"n\": null\n}";
+        assert_eq!(
+            object_members(json).unwrap(),
+            vec![
+                ("z".to_string(), "\"a, b\"".to_string()),
+                ("a\"b".to_string(), "[1,{\"k\":\"v w\"}]".to_string()),
+                ("n".to_string(), "null".to_string()),
+            ]
+        );
+        assert_eq!(object_members("{}").unwrap(), vec![]);
+        assert_eq!(object_members("[1, 2]"), None);
+        assert_eq!(object_members("{\"id\": {{ID}}}"), None);
+    }
+}
@@ -1,58 +1,363 @@
 mod parser;
 mod environment;
-mod curl;
+mod render;
+mod collection;
+mod glob;
+mod dotenv;
+mod secrets;
+mod dynamic;
+mod template;
+mod resolver;
+mod escape;
+mod redact;
+mod explain;
+mod shell;
+mod json;
+mod project;
+mod proxy;
+mod tls;
+mod cookies;
 
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
-use clap::Parser;
+use std::path::{Path, PathBuf};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 
-use crate::parser::parse_bru_file;
-use crate::environment::{load_environment, apply_environment};
-use crate::curl::{generate_curl, CurlOptions};
+use crate::parser::{parse_bru_file, ast::{BruFile, Environment}};
+use crate::collection::Collection;
+use crate::cookies::load_cookies;
+use crate::dynamic::DynamicVars;
+use crate::resolver::{load_collection_vars, load_folder_vars, load_var_file, parse_var_override, Resolver, Source};
+use crate::secrets::{resolve_secrets, SecretSource, DEFAULT_SECRETS_FILE};
+use crate::environment::{
+    apply_environment, environment_path, substitute_text, global_environment_path, load_environment, load_global_environment,
+    load_process_env, Substituted,
+};
+use crate::render::{Curl, CurlConfig, Fetch, Httpie, Python, RenderOptions, Renderer, Reqwest};
+use crate::explain::explain;
+use crate::proxy::{proxy_settings, ProxySettings};
+use crate::redact::{RedactMode, Redaction};
+use crate::tls::tls_settings;
+use crate::shell::Shell;
 
 #[derive(Parser)]
 #[command(name = "bruq")]
 #[command(about = "Convert Bruno .bru files to curl commands")]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Cli {
-    #[arg(help = "Path to .bru file")]
-    file: PathBuf,
+    #[command(subcommand)]
+    command: Option<Command>,
 
-    #[arg(short, long, help = "Environment name (looks in environments/<NAME>.bru)")]
-    env: Option<String>,
+    #[arg(required = true, help = "Path to .bru file")]
+    file: Option<PathBuf>,
+
+    #[command(flatten)]
+    input: InputArgs,
+
+    #[arg(long, help = "Fail with exit code 3 if any {{variable}} is left unresolved")]
+    strict: bool,
+
+    #[arg(long, value_enum, value_name = "MODE", num_args = 0..=1, default_missing_value = "mask", help = "Hide secret values in the output: mask (***) or placeholder ({{VAR}})")]
+    redact: Option<RedactMode>,
+
+    #[arg(long, value_enum, help = "Shell to quote the command for (default: detected from $SHELL)")]
+    shell: Option<Shell>,
+
+    #[arg(long, value_enum, default_value_t = Format::Curl, help = "Output format")]
+    format: Format,
+
+    #[arg(long, help = "Put each flag on its own line and pretty-print JSON bodies")]
+    pretty: bool,
 
     #[arg(short, long, help = "Include -v flag in curl output")]
     verbose: bool,
 
     #[arg(short, long, help = "Include -s flag in curl output")]
     silent: bool,
+
+    #[arg(long, value_name = "FILE", help = "Read and update cookies in FILE across requests (curl -b FILE -c FILE)")]
+    cookie_jar: Option<String>,
+
+    #[arg(long, help = "Ignore the bruno.json proxy and HTTP(S)_PROXY/NO_PROXY; curl's own defaults apply")]
+    no_proxy_config: bool,
+
+    #[arg(last = true, value_name = "CURL_ARGS", help = "Extra arguments passed to curl as-is, e.g. -- -o out.json --max-time 5; other formats leave them out")]
+    curl_args: Vec<String>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    /// A curl command line
+    Curl,
+    /// A curl config file, for `curl -K file` or `curl -K -`
+    CurlConfig,
+    /// An HTTPie command line
+    Httpie,
+    /// A Python snippet using requests
+    Python,
+    /// A JavaScript fetch snippet (browsers, Node 18+)
+    Fetch,
+    /// A Rust snippet using async reqwest
+    Reqwest,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Show where each variable used by a request gets its value
+    Explain {
+        #[arg(help = "Path to .bru file")]
+        file: PathBuf,
+
+        #[command(flatten)]
+        input: InputArgs,
+    },
+}
+
+/// Options that decide which variables a request sees.
+#[derive(Args)]
+struct InputArgs {
+    #[arg(short, long, help = "Environment name (looks in environments/<NAME>.bru)")]
+    env: Option<String>,
+
+    #[arg(long, value_name = "NAME|PATH", help = "Global environment layered under -e (looks in ~/.config/bruq/globals/<NAME>.bru)")]
+    global_env: Option<String>,
+
+    #[arg(long = "var", value_name = "KEY=VALUE", value_parser = parse_var_override, help = "Override a variable (repeatable, highest precedence)")]
+    vars: Vec<(String, String)>,
+
+    #[arg(long = "var-file", value_name = "PATH", help = "Read KEY=VALUE overrides from a dotenv-style file (repeatable)")]
+    var_files: Vec<PathBuf>,
+
+    #[arg(long, value_name = "SOURCE", help = "Where vars:secret values come from: file:<path>, env:<prefix> or cmd:<command> (default: file:.env.secrets)")]
+    secrets: Option<SecretSource>,
+
+    #[arg(long = "secret-pattern", value_name = "GLOB", help = "Treat variables matching GLOB as secret (repeatable, replaces the defaults)")]
+    secret_patterns: Vec<String>,
+
+    #[arg(long, help = "Seed for {{$random...}} values and the {{$timestamp}} clock, for reproducible output")]
+    seed: Option<u64>,
+}
+
+/// A parsed request together with everything needed to fill in its variables.
+struct LoadedRequest {
+    collection: Collection,
+    bru: BruFile,
+    resolver: Resolver,
+    secret_names: HashSet<String>,
+}
+
+/// An environment and the values the secret store has for its `vars:secret` keys.
+type EnvWithSecrets = (Environment, HashMap<String, String>);
+
+const EXIT_UNRESOLVED: i32 = 3;
+
+enum RunError {
+    Failed(String),
+    Unresolved(usize),
+}
+
+impl From<String> for RunError {
+    fn from(message: String) -> Self {
+        RunError::Failed(message)
+    }
 }
 
 fn main() {
-    if let Err(e) = run() {
-        eprintln!("Error: {}", e);
-        std::process::exit(1);
+    match run() {
+        Ok(()) => {}
+        Err(RunError::Failed(e)) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+        Err(RunError::Unresolved(count)) => {
+            eprintln!("Error: {} unresolved variable(s) in strict mode", count);
+            std::process::exit(EXIT_UNRESOLVED);
+        }
     }
 }
 
-fn run() -> Result<(), String> {
+fn run() -> Result<(), RunError> {
     let cli = Cli::parse();
 
-    let content = fs::read_to_string(&cli.file)
-        .map_err(|e| format!("Cannot read file: {}", e))?;
+    if let Some(Command::Explain { file, input }) = &cli.command {
+        let loaded = load_request(file, input)?;
+        let redaction = Redaction::new(RedactMode::Mask, loaded.secret_names, &input.secret_patterns);
+        let mut dynamic = DynamicVars::new(input.seed);
+        print!("{}", explain(&loaded.bru, &loaded.resolver, &mut dynamic, &redaction)?);
+        return Ok(());
+    }
+
+    let file = cli.file.as_deref().ok_or_else(|| "No .bru file given".to_string())?;
+    let LoadedRequest { collection, mut bru, resolver, secret_names } = load_request(file, &cli.input)?;
+
+    let redaction = cli
+        .redact
+        .map(|mode| Redaction::new(mode, secret_names, &cli.input.secret_patterns));
 
-    let mut bru = parse_bru_file(&content)?;
+    let mut dynamic = DynamicVars::new(cli.input.seed);
+    let Substituted { mut unresolved, kept } = apply_environment(&mut bru, &resolver, &mut dynamic, redaction.as_ref())?;
 
-    if let Some(env_name) = &cli.env {
-        let env = load_environment(&cli.file, env_name)?;
-        apply_environment(&mut bru, &env);
+    bru.cookies = load_cookies(&collection.project.cookies, &collection.root, &bru.resolved_url())?;
+    if let Some(redaction) = &redaction {
+        redaction.apply(&mut bru, &kept);
     }
 
-    let options = CurlOptions {
+    let proxy = if cli.no_proxy_config {
+        ProxySettings::default()
+    } else {
+        let mut config = collection.config.proxy.clone();
+        if let Some(config) = &mut config {
+            config.visit_text_mut(&mut |location, text| {
+                substitute_text(text, location, &resolver, &mut dynamic, redaction.as_ref(), &mut unresolved)
+            })?;
+        }
+        let mut proxy = proxy_settings(config.as_ref(), &bru.resolved_url(), |name| std::env::var(name).ok());
+        if redaction.is_some() {
+            proxy.redact();
+        }
+        proxy
+    };
+
+    let mut certificates = collection.config.client_certificates.clone();
+    if let Some(certificates) = &mut certificates {
+        certificates.visit_text_mut(&mut |location, text| {
+            substitute_text(text, location, &resolver, &mut dynamic, redaction.as_ref(), &mut unresolved)
+        })?;
+    }
+    let mut tls = tls_settings(certificates.as_ref(), &collection.project.tls, &bru.resolved_url(), &collection.root);
+    if redaction.is_some() {
+        tls.redact();
+    }
+
+    for item in &unresolved {
+        eprintln!("Warning: unresolved variable {}", item);
+    }
+    if cli.strict && !unresolved.is_empty() {
+        return Err(RunError::Unresolved(unresolved.len()));
+    }
+
+    // `.bruq.json` curl flags mean nothing to the other clients.
+    let project_args: &[String] = match cli.format {
+        Format::Curl | Format::CurlConfig => &collection.project.curl.args,
+        _ => &[],
+    };
+
+    let options = RenderOptions {
         verbose: cli.verbose,
         silent: cli.silent,
+        shell: cli.shell.unwrap_or_else(Shell::from_env),
+        pretty: cli.pretty,
+        proxy,
+        tls,
+        cookie_jar: cli.cookie_jar.clone(),
+        extra_args: project_args.iter().chain(&cli.curl_args).cloned().collect(),
     };
 
-    println!("{}", generate_curl(&bru, &options));
+    let renderer: &dyn Renderer = match cli.format {
+        Format::Curl => &Curl,
+        Format::CurlConfig => &CurlConfig,
+        Format::Httpie => &Httpie,
+        Format::Python => &Python,
+        Format::Fetch => &Fetch,
+        Format::Reqwest => &Reqwest,
+    };
+    for warning in renderer.warnings(&bru, &options) {
+        eprintln!("Warning: {}", warning);
+    }
+    println!("{}", renderer.render(&bru, &options));
 
     Ok(())
 }
+
+fn load_request(file: &Path, input: &InputArgs) -> Result<LoadedRequest, String> {
+    let content = fs::read_to_string(file)
+        .map_err(|e| format!("Cannot read file: {}", e))?;
+
+    let collection = Collection::load(file)?;
+
+    let bru = parse_bru_file(&content)?;
+
+    let env = match &input.env {
+        Some(env_name) => Some(load_environment(file, env_name)?),
+        None => None,
+    };
+    let global_env = match &input.global_env {
+        Some(spec) => Some(load_global_environment(spec)?),
+        None => None,
+    };
+
+    let mut missing = Vec::new();
+    let mut with_secrets = |loaded: Option<Environment>| -> Result<Option<EnvWithSecrets>, String> {
+        let Some(loaded) = loaded else { return Ok(None) };
+        let (values, keys) = resolve_secrets(&loaded, input.secrets.as_ref(), &collection.root)?;
+        missing.extend(keys);
+        Ok(Some((loaded, values)))
+    };
+    let env = with_secrets(env)?;
+    let global_env = with_secrets(global_env)?;
+    if !missing.is_empty() {
+        eprintln!(
+            "Warning: no value for secret variables {} (looked in {})",
+            missing.join(", "),
+            secret_store_name(input)
+        );
+    }
+
+    let secret_names = env
+        .iter()
+        .chain(global_env.iter())
+        .flat_map(|(loaded, _)| loaded.secrets.iter().cloned())
+        .collect();
+
+    let resolver = build_resolver(file, input, &collection, &bru, env, global_env)?;
+
+    Ok(LoadedRequest { collection, bru, resolver, secret_names })
+}
+
+fn secret_store_name(input: &InputArgs) -> String {
+    input
+        .secrets
+        .as_ref()
+        .map(SecretSource::describe)
+        .unwrap_or_else(|| format!("file {}", DEFAULT_SECRETS_FILE))
+}
+
+fn build_resolver(
+    file: &Path,
+    input: &InputArgs,
+    collection: &Collection,
+    bru: &BruFile,
+    env: Option<EnvWithSecrets>,
+    global_env: Option<EnvWithSecrets>,
+) -> Result<Resolver, String> {
+    let mut resolver = Resolver::default();
+
+    resolver.push(Source::Cli, input.vars.iter().cloned().collect());
+    for path in input.var_files.iter().rev() {
+        resolver.push_file(Source::VarFile(path.clone()), load_var_file(path)?, path.clone());
+    }
+
+    resolver.push_file(Source::Request, bru.vars.clone(), file.to_path_buf());
+    for (dir, vars) in load_folder_vars(file, &collection.root, |dir| collection.is_ignored(dir))? {
+        let folder_file = dir.join("folder.bru");
+        resolver.push_file(Source::Folder(dir), vars, folder_file);
+    }
+    resolver.push_file(
+        Source::Collection,
+        load_collection_vars(&collection.root)?,
+        collection.root.join("collection.bru"),
+    );
+
+    if let (Some(env_name), Some((env, secrets))) = (&input.env, env) {
+        resolver.push(Source::SecretStore(secret_store_name(input)), secrets);
+        resolver.push_file(Source::Environment(env_name.clone()), env.vars, environment_path(file, env_name)?);
+    }
+    if let (Some(spec), Some((global_env, secrets))) = (&input.global_env, global_env) {
+        resolver.push(Source::SecretStore(secret_store_name(input)), secrets);
+        resolver.push_file(Source::Global(spec.clone()), global_env.vars, global_environment_path(spec)?);
+    }
+
+    resolver.push(Source::ProcessEnv, load_process_env(&collection.root)?);
+
+    Ok(resolver)
+}
@@ -1,26 +1,181 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct BruFile {
     pub request: Request,
     pub body: Option<Body>,
-    pub headers: HashMap<String, String>,
+    pub headers: Vec<(String, String)>,
+    pub vars: HashMap<String, String>,
+    pub query: Vec<(String, String)>,
+    pub path_params: Vec<(String, String)>,
+    pub auth: Option<Auth>,
+    pub settings: BTreeMap<String, String>,
+    /// Cookies to send, filled from project config rather than the .bru file.
+    pub cookies: Vec<(String, String)>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct Request {
     pub method: Method,
     pub url: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct Body {
     pub body_type: String,
     pub content: String,
+    pub fields: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Auth {
+    Basic { username: String, password: String },
+    Bearer { token: String },
+}
+
+/// The part of a request a piece of text belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    Url,
+    Header,
+    Query,
+    PathParam,
+    Auth,
+    Body,
+    FormField,
+    Setting,
+}
+
+/// Where a visited piece of text sits in the request. `key` names the header,
+/// param, field or setting (the body type for `Part::Body`); `index` is its
+/// position in that list; `is_name` is set when the text is that key itself
+/// rather than its value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Location {
+    pub part: Part,
+    pub key: Option<String>,
+    pub index: usize,
+    pub is_name: bool,
+}
+
+impl Location {
+    fn value(part: Part, key: &str) -> Location {
+        Location { part, key: Some(key.to_string()), index: 0, is_name: false }
+    }
+
+    fn name(part: Part, key: &str) -> Location {
+        Location { part, key: Some(key.to_string()), index: 0, is_name: true }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self.part {
+            Part::Url => return write!(f, "url"),
+            Part::Body => return write!(f, "body"),
+            Part::Header => "header",
+            Part::Query => "query param",
+            Part::PathParam => "path param",
+            Part::Auth => "auth",
+            Part::FormField => "form field",
+            Part::Setting => "setting",
+        };
+        let key = self.key.as_deref().unwrap_or_default();
+        if self.is_name {
+            write!(f, "{} name {}", label, key)
+        } else {
+            write!(f, "{} {}", label, key)
+        }
+    }
+}
+
+impl BruFile {
+    /// Calls `visit` on every piece of text in the request that may contain
+    /// `{{variables}}`: url, header names and values, params, auth fields,
+    /// body, form fields (including file paths) and settings.
+    pub fn visit_text_mut<E, F>(&mut self, visit: &mut F) -> Result<(), E>
+    where
+        F: FnMut(&Location, &mut String) -> Result<(), E>,
+    {
+        visit(&Location { part: Part::Url, key: None, index: 0, is_name: false }, &mut self.request.url)?;
+
+        visit_pairs(&mut self.headers, Part::Header, visit)?;
+
+        visit_pairs(&mut self.query, Part::Query, visit)?;
+        visit_pairs(&mut self.path_params, Part::PathParam, visit)?;
+
+        match &mut self.auth {
+            Some(Auth::Basic { username, password }) => {
+                visit(&Location::value(Part::Auth, "username"), username)?;
+                visit(&Location::value(Part::Auth, "password"), password)?;
+            }
+            Some(Auth::Bearer { token }) => visit(&Location::value(Part::Auth, "token"), token)?,
+            None => {}
+        }
+
+        if let Some(body) = &mut self.body {
+            visit(&Location::value(Part::Body, &body.body_type), &mut body.content)?;
+            visit_pairs(&mut body.fields, Part::FormField, visit)?;
+        }
+
+        for (index, (key, value)) in self.settings.iter_mut().enumerate() {
+            visit(&Location { index, ..Location::value(Part::Setting, key) }, value)?;
+        }
+
+        Ok(())
+    }
+
+    /// The value of the first header called `name`, ignoring case.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find(|(key, _)| key.eq_ignore_ascii_case(name)).map(|(_, value)| value.as_str())
+    }
+
+    /// The request URL with `:name` path segments replaced by `params:path`.
+    pub fn resolved_url(&self) -> String {
+        if self.path_params.is_empty() {
+            return self.request.url.clone();
+        }
+
+        let (path, query) = match self.request.url.split_once('?') {
+            Some((path, query)) => (path, Some(query)),
+            None => (self.request.url.as_str(), None),
+        };
+
+        let segments: Vec<String> = path
+            .split('/')
+            .map(|segment| {
+                segment
+                    .strip_prefix(':')
+                    .and_then(|name| self.path_params.iter().find(|(key, _)| key == name))
+                    .map(|(_, value)| value.clone())
+                    .unwrap_or_else(|| segment.to_string())
+            })
+            .collect();
+
+        let mut url = segments.join("/");
+        if let Some(query) = query {
+            url.push('?');
+            url.push_str(query);
+        }
+        url
+    }
+}
+
+fn visit_pairs<E, F>(pairs: &mut [(String, String)], part: Part, visit: &mut F) -> Result<(), E>
+where
+    F: FnMut(&Location, &mut String) -> Result<(), E>,
+{
+    for (index, (name, value)) in pairs.iter_mut().enumerate() {
+        visit(&Location { index, ..Location::value(part, name) }, value)?;
+        visit(&Location { index, ..Location::name(part, name) }, name)?;
+    }
+    Ok(())
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Default)]
 pub enum Method {
+    #[default]
     Get,
     Post,
     Put,
@@ -64,4 +219,5 @@ impl std::str::FromStr for Method {
 #[derive(Debug, Clone, Default)]
 pub struct Environment {
     pub vars: HashMap<String, String>,
+    pub secrets: Vec<String>,
 }
@@ -1,14 +1,21 @@
 pub mod ast;
 
-use std::collections::HashMap;
-use ast::{BruFile, Request, Body, Method, Environment};
+use std::collections::{BTreeMap, HashMap};
+use ast::{Auth, BruFile, Request, Body, Method, Environment};
 
 type CharIter<'a> = std::iter::Peekable<std::str::Chars<'a>>;
 
 pub fn parse_bru_file(content: &str) -> Result<BruFile, String> {
     let mut request: Option<Request> = None;
-    let mut body: Option<Body> = None;
-    let mut headers: HashMap<String, String> = HashMap::new();
+    let mut body_mode: Option<String> = None;
+    let mut auth_mode: Option<String> = None;
+    let mut bodies: Vec<Body> = Vec::new();
+    let mut auths: Vec<(String, HashMap<String, String>)> = Vec::new();
+    let mut headers: Vec<(String, String)> = Vec::new();
+    let mut vars: HashMap<String, String> = HashMap::new();
+    let mut query: Vec<(String, String)> = Vec::new();
+    let mut path_params: Vec<(String, String)> = Vec::new();
+    let mut settings: BTreeMap<String, String> = BTreeMap::new();
 
     let mut chars = content.chars().peekable();
 
@@ -23,17 +30,32 @@ pub fn parse_bru_file(content: &str) -> Result<BruFile, String> {
 
         skip_whitespace_no_newline(&mut chars);
 
-        if block_name == "body" && chars.peek() == Some(&':') {
+        if block_name == "vars" && chars.peek() == Some(&':') {
+            if let Some(pre_request) = parse_vars_phase_block(&mut chars) {
+                vars = pre_request;
+            }
+            continue;
+        }
+
+        if chars.peek() == Some(&':') {
             chars.next();
-            let body_type = read_identifier(&mut chars);
+            let kind = read_identifier(&mut chars);
             skip_whitespace(&mut chars);
-            if chars.peek() == Some(&'{') {
-                chars.next();
-                let content_str = read_balanced_braces(&mut chars);
-                body = Some(Body {
-                    body_type,
-                    content: content_str.trim().to_string(),
-                });
+            if chars.peek() != Some(&'{') {
+                skip_line(&mut chars);
+                continue;
+            }
+            chars.next();
+
+            match (block_name.as_str(), kind.as_str()) {
+                ("body", _) => {
+                    let content_str = read_balanced_braces(&mut chars);
+                    bodies.push(parse_body(kind, content_str.trim()));
+                }
+                ("params", "query") => query = parse_key_value_list(&mut chars),
+                ("params", "path") => path_params = parse_key_value_list(&mut chars),
+                ("auth", _) => auths.push((kind, parse_key_value_block(&mut chars))),
+                _ => skip_block(&mut chars),
             }
             continue;
         }
@@ -46,20 +68,128 @@ pub fn parse_bru_file(content: &str) -> Result<BruFile, String> {
 
         match block_name.as_str() {
             "get" | "post" | "put" | "delete" | "patch" | "options" | "head" => {
-                request = Some(parse_method_block(&block_name, &mut chars)?);
+                let entries = parse_key_value_block(&mut chars);
+                body_mode = entries.get("body").cloned();
+                auth_mode = entries.get("auth").cloned();
+                request = Some(parse_method_block(&block_name, &entries)?);
             }
-            "headers" => headers = parse_key_value_block(&mut chars),
+            "headers" => headers = parse_key_value_list(&mut chars),
+            "settings" => settings = parse_key_value_list(&mut chars).into_iter().collect(),
             _ => skip_block(&mut chars),
         }
     }
 
     let request = request.ok_or("No request method block found")?;
 
-    Ok(BruFile { request, body, headers })
+    let body = match body_mode.as_deref() {
+        Some("none") => None,
+        Some(mode) => bodies.into_iter().find(|b| b.body_type == mode),
+        None => bodies.pop(),
+    };
+
+    let auth_block = match auth_mode.as_deref() {
+        Some("none") | Some("inherit") => None,
+        Some(mode) => auths.into_iter().find(|(kind, _)| kind == mode),
+        None => auths.pop(),
+    };
+    let auth = auth_block.and_then(|(kind, entries)| parse_auth(&kind, &entries));
+
+    Ok(BruFile { request, body, headers, vars, query, path_params, auth, settings, cookies: Vec::new() })
+}
+
+fn parse_body(body_type: String, content: &str) -> Body {
+    let fields = match body_type.as_str() {
+        "form-urlencoded" | "multipart-form" => content
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+            // Bruno marks disabled fields with a leading `~`.
+            .filter(|(key, _)| !key.is_empty() && !key.starts_with('~'))
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    Body { body_type, content: content.to_string(), fields }
+}
+
+fn parse_auth(kind: &str, entries: &HashMap<String, String>) -> Option<Auth> {
+    let field = |name: &str| entries.get(name).cloned().unwrap_or_default();
+    match kind {
+        "basic" => Some(Auth::Basic { username: field("username"), password: field("password") }),
+        "bearer" => Some(Auth::Bearer { token: field("token") }),
+        _ => None,
+    }
+}
+
+/// Extracts the `vars:pre-request` block from a `folder.bru` or
+/// `collection.bru` file.
+pub fn parse_pre_request_vars(content: &str) -> HashMap<String, String> {
+    let mut vars: HashMap<String, String> = HashMap::new();
+    let mut chars = content.chars().peekable();
+
+    while chars.peek().is_some() {
+        skip_whitespace(&mut chars);
+
+        let block_name = read_identifier(&mut chars);
+        if block_name.is_empty() {
+            skip_line(&mut chars);
+            continue;
+        }
+
+        if block_name == "vars" && chars.peek() == Some(&':') {
+            if let Some(pre_request) = parse_vars_phase_block(&mut chars) {
+                vars = pre_request;
+            }
+            continue;
+        }
+
+        skip_whitespace_no_newline(&mut chars);
+
+        match chars.peek() {
+            Some('{') => {
+                chars.next();
+                skip_block(&mut chars);
+            }
+            Some(':') => {
+                chars.next();
+                read_identifier(&mut chars);
+                skip_whitespace(&mut chars);
+                if chars.peek() == Some(&'{') {
+                    chars.next();
+                    skip_block(&mut chars);
+                }
+            }
+            _ => skip_line(&mut chars),
+        }
+    }
+
+    vars
+}
+
+/// Parses the rest of a `vars:<phase> { ... }` block, positioned at the colon.
+/// Returns the entries for `pre-request`; other phases are skipped.
+fn parse_vars_phase_block(chars: &mut CharIter) -> Option<HashMap<String, String>> {
+    chars.next();
+    let phase = read_identifier(chars);
+    skip_whitespace(chars);
+
+    if chars.peek() != Some(&'{') {
+        skip_line(chars);
+        return None;
+    }
+    chars.next();
+
+    if phase == "pre-request" {
+        Some(parse_key_value_block(chars))
+    } else {
+        skip_block(chars);
+        None
+    }
 }
 
 pub fn parse_environment(content: &str) -> Result<Environment, String> {
     let mut vars: HashMap<String, String> = HashMap::new();
+    let mut secrets: Vec<String> = Vec::new();
     let mut chars = content.chars().peekable();
 
     while chars.peek().is_some() {
@@ -71,6 +201,19 @@ pub fn parse_environment(content: &str) -> Result<Environment, String> {
             continue;
         }
 
+        if block_name == "vars" && chars.peek() == Some(&':') {
+            chars.next();
+            let list_name = read_identifier(&mut chars);
+            skip_whitespace(&mut chars);
+            if list_name == "secret" && chars.peek() == Some(&'[') {
+                chars.next();
+                secrets = parse_list_block(&mut chars);
+            } else {
+                skip_line(&mut chars);
+            }
+            continue;
+        }
+
         skip_whitespace_no_newline(&mut chars);
 
         if chars.peek() != Some(&'{') {
@@ -86,18 +229,21 @@ pub fn parse_environment(content: &str) -> Result<Environment, String> {
         vars = parse_key_value_block(&mut chars);
     }
 
-    Ok(Environment { vars })
+    Ok(Environment { vars, secrets })
 }
 
-fn parse_method_block(method_str: &str, chars: &mut CharIter) -> Result<Request, String> {
-    let entries = parse_key_value_block(chars);
+fn parse_method_block(method_str: &str, entries: &HashMap<String, String>) -> Result<Request, String> {
     let method: Method = method_str.parse()?;
     let url = entries.get("url").cloned().unwrap_or_default();
     Ok(Request { method, url })
 }
 
 fn parse_key_value_block(chars: &mut CharIter) -> HashMap<String, String> {
-    let mut result = HashMap::new();
+    parse_key_value_list(chars).into_iter().collect()
+}
+
+fn parse_key_value_list(chars: &mut CharIter) -> Vec<(String, String)> {
+    let mut result = Vec::new();
 
     loop {
         skip_whitespace(chars);
@@ -124,17 +270,45 @@ fn parse_key_value_block(chars: &mut CharIter) -> HashMap<String, String> {
         skip_whitespace_no_newline(chars);
         let value = read_line(chars);
 
-        result.insert(key.trim().to_string(), value.trim().to_string());
+        // Bruno marks disabled entries with a leading `~`.
+        if key.trim().starts_with('~') {
+            continue;
+        }
+        result.push((key.trim().to_string(), value.trim().to_string()));
     }
 
     result
 }
 
+fn parse_list_block(chars: &mut CharIter) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+
+    for c in chars.by_ref() {
+        match c {
+            ']' => break,
+            ',' | '\n' => {
+                if !current.trim().is_empty() {
+                    items.push(current.trim().to_string());
+                }
+                current.clear();
+            }
+            _ => current.push(c),
+        }
+    }
+
+    if !current.trim().is_empty() {
+        items.push(current.trim().to_string());
+    }
+
+    items
+}
+
 fn read_balanced_braces(chars: &mut CharIter) -> String {
     let mut result = String::new();
     let mut depth = 1;
 
-    while let Some(c) = chars.next() {
+    for c in chars.by_ref() {
         match c {
             '{' => {
                 depth += 1;
@@ -156,7 +330,7 @@ fn read_balanced_braces(chars: &mut CharIter) -> String {
 
 fn skip_block(chars: &mut CharIter) {
     let mut depth = 1;
-    while let Some(c) = chars.next() {
+    for c in chars.by_ref() {
         match c {
             '{' => depth += 1,
             '}' if depth == 1 => break,
@@ -179,7 +353,7 @@ fn skip_whitespace_no_newline(chars: &mut CharIter) {
 }
 
 fn skip_line(chars: &mut CharIter) {
-    while let Some(c) = chars.next() {
+    for c in chars.by_ref() {
         if c == '\n' {
             break;
         }
@@ -212,7 +386,7 @@ fn read_until_colon(chars: &mut CharIter) -> String {
 
 fn read_line(chars: &mut CharIter) -> String {
     let mut result = String::new();
-    while let Some(c) = chars.next() {
+    for c in chars.by_ref() {
         if c == '\n' {
             break;
         }
@@ -270,13 +444,127 @@ get {
 }
 
 headers {
-  Authorization: Bearer token123
   X-Custom-Header: custom-value
+  Authorization: Bearer token123
+  Accept: text/plain
+  Accept: application/json
+  ~X-Debug: 1
+}
+"#;
+        let bru = parse_bru_file(content).unwrap();
+        assert_eq!(bru.header("authorization").unwrap(), "Bearer token123");
+        assert_eq!(bru.header("X-Custom-Header").unwrap(), "custom-value");
+        let names: Vec<&str> = bru.headers.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["X-Custom-Header", "Authorization", "Accept", "Accept"]);
+    }
+
+    #[test]
+    fn parse_request_vars() {
+        let content = r#"
+get {
+  url: {{HOST}}/users/{{USER_ID}}
+}
+
+vars:pre-request {
+  USER_ID: 42
+}
+
+vars:post-response {
+  TOKEN: res.body.token
 }
 "#;
         let bru = parse_bru_file(content).unwrap();
-        assert_eq!(bru.headers.get("Authorization").unwrap(), "Bearer token123");
-        assert_eq!(bru.headers.get("X-Custom-Header").unwrap(), "custom-value");
+        assert_eq!(bru.vars.len(), 1);
+        assert_eq!(bru.vars.get("USER_ID").unwrap(), "42");
+    }
+
+    #[test]
+    fn parse_folder_pre_request_vars() {
+        let content = r#"
+meta {
+  name: users
+}
+
+body:json {
+  { "ignored": true }
+}
+
+vars:pre-request {
+  SCOPE: users
+}
+"#;
+        let vars = parse_pre_request_vars(content);
+        assert_eq!(vars.len(), 1);
+        assert_eq!(vars.get("SCOPE").unwrap(), "users");
+    }
+
+    #[test]
+    fn parse_params_auth_and_settings() {
+        let content = r#"
+get {
+  url: {{HOST}}/users/:id?page=1
+  body: none
+  auth: basic
+}
+
+params:query {
+  page: 1
+  ~limit: 10
+}
+
+params:path {
+  id: {{USER_ID}}
+}
+
+auth:basic {
+  username: bob
+  password: {{PASSWORD}}
+}
+
+auth:bearer {
+  token: unused
+}
+
+settings {
+  encodeUrl: true
+}
+"#;
+        let bru = parse_bru_file(content).unwrap();
+        assert_eq!(bru.query, vec![("page".to_string(), "1".to_string())]);
+        assert_eq!(bru.path_params, vec![("id".to_string(), "{{USER_ID}}".to_string())]);
+        assert!(matches!(bru.auth, Some(Auth::Basic { ref password, .. }) if password == "{{PASSWORD}}"));
+        assert_eq!(bru.settings.get("encodeUrl").unwrap(), "true");
+        assert!(bru.body.is_none());
+    }
+
+    #[test]
+    fn parse_form_bodies_and_body_mode() {
+        let content = r#"
+post {
+  url: https://api.example.com/upload
+  body: multipart-form
+}
+
+body:json {
+  { "stale": true }
+}
+
+body:multipart-form {
+  title: Avatar
+  ~draft: true
+  file: @file(images/avatar.png)
+}
+"#;
+        let bru = parse_bru_file(content).unwrap();
+        let body = bru.body.unwrap();
+        assert_eq!(body.body_type, "multipart-form");
+        assert_eq!(
+            body.fields,
+            vec![
+                ("title".to_string(), "Avatar".to_string()),
+                ("file".to_string(), "@file(images/avatar.png)".to_string()),
+            ]
+        );
     }
 
     #[test]
@@ -292,6 +580,25 @@ vars {
         assert_eq!(env.vars.get("API_KEY").unwrap(), "secret123");
     }
 
+    #[test]
+    fn parse_environment_secret_list() {
+        let content = r#"
+vars {
+  API_URL: https://api.example.com
+}
+vars:secret [
+  API_KEY,
+  DB_PASSWORD
+]
+"#;
+        let env = parse_environment(content).unwrap();
+        assert_eq!(env.vars.len(), 1);
+        assert_eq!(env.secrets, vec!["API_KEY", "DB_PASSWORD"]);
+
+        let inline = parse_environment("vars:secret [ TOKEN ]\n").unwrap();
+        assert_eq!(inline.secrets, vec!["TOKEN"]);
+    }
+
     #[test]
     fn parse_nested_json_body() {
         let content = r#"
@@ -0,0 +1,111 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+/// bruq's own settings, kept next to `bruno.json` so Bruno never sees them.
+pub const PROJECT_CONFIG_FILE: &str = ".bruq.json";
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ProjectConfig {
+    #[serde(default)]
+    pub curl: CurlDefaults,
+    #[serde(default)]
+    pub tls: TlsDefaults,
+    #[serde(default)]
+    pub cookies: CookieDefaults,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CurlDefaults {
+    /// Added to every generated command, before arguments given after `--`.
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+/// Cookies sent with every request.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CookieDefaults {
+    /// A Netscape cookie file, relative to the collection root.
+    pub file: Option<String>,
+    #[serde(default)]
+    pub values: BTreeMap<String, String>,
+}
+
+/// TLS settings Bruno keeps in its app preferences rather than bruno.json.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct TlsDefaults {
+    /// CA bundle for `--cacert`, relative to the collection root.
+    pub ca_cert: Option<String>,
+    /// `false` adds `--insecure`.
+    #[serde(default = "default_ssl_verification")]
+    pub ssl_verification: bool,
+}
+
+impl Default for TlsDefaults {
+    fn default() -> Self {
+        TlsDefaults { ca_cert: None, ssl_verification: true }
+    }
+}
+
+fn default_ssl_verification() -> bool {
+    true
+}
+
+impl ProjectConfig {
+    pub fn load(collection_root: &Path) -> Result<ProjectConfig, String> {
+        let path = collection_root.join(PROJECT_CONFIG_FILE);
+        if !path.exists() {
+            return Ok(ProjectConfig::default());
+        }
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Cannot read {}: {}", PROJECT_CONFIG_FILE, e))?;
+        parse_project_config(&content)
+    }
+}
+
+pub fn parse_project_config(content: &str) -> Result<ProjectConfig, String> {
+    if content.trim().is_empty() {
+        return Ok(ProjectConfig::default());
+    }
+    serde_json::from_str(content).map_err(|e| format!("Invalid {}: {}", PROJECT_CONFIG_FILE, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn parse_curl_defaults() {
+        let config = parse_project_config(r#"{ "curl": { "args": ["-i", "-L", "--max-time", "10"] } }"#).unwrap();
+        assert_eq!(config.curl.args, vec!["-i", "-L", "--max-time", "10"]);
+    }
+
+    #[test]
+    fn parse_cookie_sources() {
+        let config = parse_project_config(r#"{ "cookies": { "file": "cookies.txt", "values": { "theme": "dark" } } }"#).unwrap();
+        assert_eq!(config.cookies.file.as_deref(), Some("cookies.txt"));
+        assert_eq!(config.cookies.values.get("theme").unwrap(), "dark");
+    }
+
+    #[test]
+    fn unknown_keys_are_an_error() {
+        let err = parse_project_config(r#"{ "curl": { "flags": ["-k"] } }"#).unwrap_err();
+        assert!(err.contains("Invalid .bruq.json"));
+    }
+
+    #[test]
+    fn missing_file_means_defaults() {
+        let temp = TempDir::new().unwrap();
+        assert!(ProjectConfig::load(temp.path()).unwrap().curl.args.is_empty());
+
+        fs::write(temp.path().join(PROJECT_CONFIG_FILE), r#"{ "curl": { "args": ["-k"] } }"#).unwrap();
+        assert_eq!(ProjectConfig::load(temp.path()).unwrap().curl.args, vec!["-k"]);
+    }
+}
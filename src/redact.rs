@@ -0,0 +1,209 @@
+use std::collections::HashSet;
+
+use clap::ValueEnum;
+
+use crate::glob::glob_match;
+use crate::parser::ast::{Auth, BruFile, Location, Part};
+
+pub const MASK: &str = "***";
+
+pub const DEFAULT_SECRET_PATTERNS: [&str; 6] =
+    ["*TOKEN*", "*SECRET*", "*PASSWORD*", "*PASSWD*", "*API_KEY*", "*APIKEY*"];
+
+const SENSITIVE_HEADERS: [&str; 6] = [
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+    "x-api-key",
+    "api-key",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum RedactMode {
+    /// Replace secret values with ***
+    Mask,
+    /// Keep the {{VAR}} placeholder instead of the value
+    Placeholder,
+}
+
+/// Which values to hide from the generated output, and how.
+#[derive(Debug, Clone)]
+pub struct Redaction {
+    pub mode: RedactMode,
+    pub secret_names: HashSet<String>,
+    pub patterns: Vec<String>,
+}
+
+impl Redaction {
+    /// `secret_names` come from `vars:secret` lists; a variable is also secret
+    /// when its name matches one of `patterns` (case-insensitive globs).
+    pub fn new(mode: RedactMode, secret_names: HashSet<String>, patterns: &[String]) -> Redaction {
+        let patterns = if patterns.is_empty() {
+            DEFAULT_SECRET_PATTERNS.iter().map(|p| p.to_string()).collect()
+        } else {
+            patterns.to_vec()
+        };
+        Redaction {
+            mode,
+            secret_names,
+            patterns: patterns.iter().map(|p| p.to_uppercase()).collect(),
+        }
+    }
+
+    pub fn is_secret(&self, name: &str) -> bool {
+        if self.secret_names.contains(name) {
+            return true;
+        }
+        let upper = name.to_uppercase();
+        self.patterns.iter().any(|pattern| glob_match(pattern, &upper))
+    }
+
+    /// What a secret `{{name}}` is replaced with.
+    pub fn replacement(&self, name: &str) -> String {
+        match self.mode {
+            RedactMode::Mask => MASK.to_string(),
+            RedactMode::Placeholder => format!("{{{{{}}}}}", name),
+        }
+    }
+
+    /// Masks credentials that do not come from secret variables: sensitive
+    /// headers, cookies, basic-auth passwords and bearer tokens. In placeholder
+    /// mode a value at one of the `kept` locations, where substitution left a
+    /// secret `{{placeholder}}`, is left alone.
+    pub fn apply(&self, bru: &mut BruFile, kept: &[Location]) {
+        let keeps = |part: Part, matches: &dyn Fn(&Location) -> bool| {
+            self.mode == RedactMode::Placeholder
+                && kept.iter().any(|location| location.part == part && !location.is_name && matches(location))
+        };
+
+        for (index, (name, value)) in bru.headers.iter_mut().enumerate() {
+            if is_sensitive_header(name) && !keeps(Part::Header, &|location| location.index == index) {
+                *value = if name.eq_ignore_ascii_case("cookie") {
+                    mask_cookies(value)
+                } else {
+                    mask_keeping_scheme(value)
+                };
+            }
+        }
+
+        // Cookies come from the cookie sources after substitution, so they never hold a placeholder.
+        for (_, value) in bru.cookies.iter_mut() {
+            *value = MASK.to_string();
+        }
+
+        let auth_kept = |key: &str| keeps(Part::Auth, &|location| location.key.as_deref() == Some(key));
+        match &mut bru.auth {
+            Some(Auth::Basic { password, .. }) if !auth_kept("password") => *password = MASK.to_string(),
+            Some(Auth::Bearer { token }) if !auth_kept("token") => *token = MASK.to_string(),
+            _ => {}
+        }
+    }
+}
+
+pub fn is_sensitive_header(name: &str) -> bool {
+    let lower = name.to_lowercase();
+    SENSITIVE_HEADERS.contains(&lower.as_str())
+        || lower.contains("api-key")
+        || lower.contains("apikey")
+        || lower.contains("auth-token")
+}
+
+/// "Bearer abc" becomes "Bearer ***"; a bare value becomes "***".
+fn mask_keeping_scheme(value: &str) -> String {
+    match value.split_once(' ') {
+        Some((scheme, _)) if scheme.chars().all(|c| c.is_ascii_alphabetic()) => format!("{} {}", scheme, MASK),
+        _ => MASK.to_string(),
+    }
+}
+
+fn mask_cookies(value: &str) -> String {
+    value
+        .split(';')
+        .map(|pair| match pair.split_once('=') {
+            Some((name, _)) => format!("{}={}", name.trim(), MASK),
+            None => MASK.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn redaction(mode: RedactMode) -> Redaction {
+        Redaction::new(mode, HashSet::from(["DB_CONN".to_string()]), &[])
+    }
+
+    #[test]
+    fn secret_names_and_patterns() {
+        let redaction = redaction(RedactMode::Mask);
+        assert!(redaction.is_secret("DB_CONN"));
+        assert!(redaction.is_secret("github_token"));
+        assert!(redaction.is_secret("STRIPE_API_KEY"));
+        assert!(!redaction.is_secret("BASE_URL"));
+
+        let custom = Redaction::new(RedactMode::Mask, HashSet::new(), &["*_CRED".to_string()]);
+        assert!(custom.is_secret("aws_cred"));
+        assert!(!custom.is_secret("API_TOKEN"));
+    }
+
+    #[test]
+    fn replacement_per_mode() {
+        assert_eq!(redaction(RedactMode::Mask).replacement("TOKEN"), "***");
+        assert_eq!(redaction(RedactMode::Placeholder).replacement("TOKEN"), "{{TOKEN}}");
+    }
+
+    #[test]
+    fn masks_sensitive_headers_and_auth() {
+        let headers = vec![
+            ("Authorization".to_string(), "Bearer live-token".to_string()),
+            ("Cookie".to_string(), "session=abc; theme=dark".to_string()),
+            ("X-Api-Key".to_string(), "k-123".to_string()),
+            ("Accept".to_string(), "application/json".to_string()),
+        ];
+        let mut bru = BruFile {
+            headers,
+            auth: Some(Auth::Basic { username: "bob".to_string(), password: "pw".to_string() }),
+            cookies: vec![("session".to_string(), "abc".to_string())],
+            ..Default::default()
+        };
+
+        redaction(RedactMode::Mask).apply(&mut bru, &[]);
+
+        assert_eq!(bru.header("Authorization").unwrap(), "Bearer ***");
+        assert_eq!(bru.header("Cookie").unwrap(), "session=***; theme=***");
+        assert_eq!(bru.header("X-Api-Key").unwrap(), "***");
+        assert_eq!(bru.header("Accept").unwrap(), "application/json");
+        assert_eq!(bru.cookies, vec![("session".to_string(), "***".to_string())]);
+        assert!(matches!(bru.auth, Some(Auth::Basic { ref username, ref password }) if username == "bob" && password == "***"));
+    }
+
+    #[test]
+    fn placeholder_mode_keeps_placeholders() {
+        let headers = vec![
+            ("Authorization".to_string(), "Bearer {{TOKEN}}".to_string()),
+            ("Authorization".to_string(), "Bearer {{TOKEN}}".to_string()),
+            ("X-Api-Key".to_string(), "live{{key".to_string()),
+        ];
+        let mut bru = BruFile {
+            headers,
+            auth: Some(Auth::Bearer { token: "{{TOKEN}}".to_string() }),
+            ..Default::default()
+        };
+        let kept = vec![
+            Location { part: Part::Header, key: Some("Authorization".to_string()), index: 0, is_name: false },
+            Location { part: Part::Auth, key: Some("token".to_string()), index: 0, is_name: false },
+        ];
+
+        redaction(RedactMode::Placeholder).apply(&mut bru, &kept);
+        let values: Vec<&str> = bru.headers.iter().map(|(_, value)| value.as_str()).collect();
+        assert_eq!(values, vec!["Bearer {{TOKEN}}", "Bearer ***", "***"]);
+        assert!(matches!(bru.auth, Some(Auth::Bearer { ref token }) if token == "{{TOKEN}}"));
+
+        let mut bru = BruFile { auth: Some(Auth::Bearer { token: "{{TOKEN}}".to_string() }), ..Default::default() };
+        redaction(RedactMode::Mask).apply(&mut bru, &kept);
+        assert!(matches!(bru.auth, Some(Auth::Bearer { ref token }) if token == "***"));
+    }
+}
@@ -0,0 +1,644 @@
+use crate::json::pretty_print;
+use crate::parser::ast::{Auth, BruFile, Method};
+
+use super::{default_content_type, file_path, has_header, RenderOptions, Renderer};
+
+/// A curl command line.
+pub struct Curl;
+
+/// A curl config file, for `curl -K file` or `curl -K -`.
+pub struct CurlConfig;
+
+impl Renderer for Curl {
+    fn render(&self, bru: &BruFile, options: &RenderOptions) -> String {
+        generate_curl(bru, options)
+    }
+
+    fn warnings(&self, bru: &BruFile, options: &RenderOptions) -> Vec<String> {
+        options.shell.line_break_warning(&curl_command(bru, options)).into_iter().collect()
+    }
+}
+
+impl Renderer for CurlConfig {
+    fn render(&self, bru: &BruFile, options: &RenderOptions) -> String {
+        generate_curl_config(bru, options)
+    }
+}
+
+/// One curl option. `flag` is how it is written on the command line and
+/// `name` is its long name, which curl config files use as the key. The URL
+/// has no flag: it is a bare argument on the command line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurlArg {
+    pub flag: Option<String>,
+    pub name: String,
+    pub value: Option<String>,
+}
+
+impl CurlArg {
+    fn switch(flag: &str, name: &str) -> CurlArg {
+        CurlArg { flag: Some(flag.to_string()), name: name.to_string(), value: None }
+    }
+
+    fn option(flag: &str, name: &str, value: String) -> CurlArg {
+        CurlArg { flag: Some(flag.to_string()), name: name.to_string(), value: Some(value) }
+    }
+
+    fn url(url: String) -> CurlArg {
+        CurlArg { flag: None, name: "url".to_string(), value: Some(url) }
+    }
+
+    /// Groups arguments given verbatim (`-o out.json -i`) into options,
+    /// pairing each option that takes a value with the word after it. Other
+    /// words, such as extra URLs, stand on their own.
+    fn verbatim(args: &[String]) -> Vec<CurlArg> {
+        let mut result = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if arg.starts_with('-') {
+                let value = if takes_value(arg) { args.next().cloned() } else { None };
+                result.push(CurlArg { flag: Some(arg.clone()), name: arg.clone(), value });
+            } else {
+                result.push(CurlArg::url(arg.clone()));
+            }
+        }
+        result
+    }
+
+    /// The words this option adds to a command line.
+    pub fn words(&self) -> Vec<String> {
+        self.flag.clone().into_iter().chain(self.value.clone()).collect()
+    }
+
+    /// The option as a curl config file line, e.g. `header = "Accept: */*"`.
+    /// Options written with dashes take their value after a space instead.
+    pub fn config_line(&self) -> String {
+        let separator = if self.name.starts_with('-') { " " } else { " = " };
+        match &self.value {
+            Some(value) => format!("{}{}\"{}\"", self.name, separator, escape_config(value)),
+            None => self.name.clone(),
+        }
+    }
+}
+
+pub fn generate_curl(bru: &BruFile, options: &RenderOptions) -> String {
+    let program = vec![options.shell.curl_program().to_string()];
+    let args = curl_args(bru, options);
+
+    if options.pretty {
+        let lines: Vec<Vec<String>> = std::iter::once(program).chain(args.iter().map(CurlArg::words)).collect();
+        options.shell.join_lines(&lines)
+    } else {
+        options.shell.join(&curl_command(bru, options))
+    }
+}
+
+/// Writes the request as a curl config file, for `curl -K file` or
+/// `curl -K -`. Nothing goes through a shell, so nothing needs shell quoting.
+pub fn generate_curl_config(bru: &BruFile, options: &RenderOptions) -> String {
+    curl_args(bru, options)
+        .iter()
+        .map(CurlArg::config_line)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The full curl command as an argument vector, before any shell quoting.
+pub fn curl_command(bru: &BruFile, options: &RenderOptions) -> Vec<String> {
+    std::iter::once(options.shell.curl_program().to_string())
+        .chain(curl_args(bru, options).iter().flat_map(CurlArg::words))
+        .collect()
+}
+
+/// The options curl needs to send the request, in command-line order.
+pub fn curl_args(bru: &BruFile, options: &RenderOptions) -> Vec<CurlArg> {
+    let mut parts = Vec::new();
+
+    if options.verbose {
+        parts.push(CurlArg::switch("-v", "verbose"));
+    }
+
+    if options.silent {
+        parts.push(CurlArg::switch("-s", "silent"));
+    }
+
+    let method_index = parts.len();
+    parts.push(CurlArg::url(bru.resolved_url()));
+
+    let has_content_type = has_header(bru, "content-type");
+    let is_head = matches!(bru.request.method, Method::Head);
+
+    // A HEAD response has no body, and curl rejects -I together with data.
+    if let Some(body) = bru.body.as_ref().filter(|_| !is_head) {
+        let is_multipart = body.body_type == "multipart-form";
+
+        // curl picks the multipart boundary itself, so never override it.
+        if !has_content_type && !is_multipart {
+            parts.push(CurlArg::option("-H", "header", format!("Content-Type: {}", default_content_type(body))));
+        }
+
+        if is_multipart {
+            // -F would read a text value starting with @ or < from a file.
+            for (name, value) in &body.fields {
+                let part = match file_path(value) {
+                    Some(path) => CurlArg::option("-F", "form", format!("{}=@{}", name, path)),
+                    None => CurlArg::option("--form-string", "form-string", format!("{}={}", name, value)),
+                };
+                parts.push(part);
+            }
+        } else if body.body_type == "form-urlencoded" {
+            for (name, value) in &body.fields {
+                parts.push(CurlArg::option("--data-urlencode", "data-urlencode", format!("{}={}", name, value)));
+            }
+        } else if !body.content.is_empty() {
+            let content = match body.body_type.as_str() {
+                "json" if options.pretty => pretty_print(&body.content).unwrap_or_else(|| body.content.clone()),
+                _ => body.content.clone(),
+            };
+            // -d would read a body starting with @ from a file.
+            let flag = if content.starts_with('@') { "--data-raw" } else { "-d" };
+            parts.push(CurlArg::option(flag, "data-raw", content));
+        }
+    }
+
+    let sends_data = parts
+        .iter()
+        .any(|arg| matches!(arg.name.as_str(), "data-raw" | "data-urlencode" | "form" | "form-string"));
+    if let Some(method) = method_arg(bru.request.method, sends_data) {
+        parts.insert(method_index, method);
+    }
+
+    let has_authorization = has_header(bru, "authorization");
+
+    match &bru.auth {
+        Some(Auth::Basic { username, password }) => {
+            parts.push(CurlArg::option("-u", "user", format!("{}:{}", username, password)));
+        }
+        Some(Auth::Bearer { token }) if !has_authorization => {
+            parts.push(CurlArg::option("-H", "header", format!("Authorization: Bearer {}", token)));
+        }
+        _ => {}
+    }
+
+    for (key, value) in &bru.headers {
+        parts.push(CurlArg::option("-H", "header", format!("{}: {}", key, value)));
+    }
+
+    if !bru.cookies.is_empty() {
+        let cookies: Vec<String> = bru.cookies.iter().map(|(name, value)| format!("{}={}", name, value)).collect();
+        parts.push(CurlArg::option("-b", "cookie", cookies.join("; ")));
+    }
+    if let Some(jar) = &options.cookie_jar {
+        parts.push(CurlArg::option("-b", "cookie", jar.clone()));
+        parts.push(CurlArg::option("-c", "cookie-jar", jar.clone()));
+    }
+
+    let proxy = &options.proxy;
+    if let Some(url) = &proxy.url {
+        parts.push(CurlArg::option("--proxy", "proxy", url.clone()));
+    }
+    if let Some(user) = &proxy.user {
+        parts.push(CurlArg::option("--proxy-user", "proxy-user", user.clone()));
+    }
+    if let Some(hosts) = &proxy.no_proxy {
+        parts.push(CurlArg::option("--noproxy", "noproxy", hosts.clone()));
+    }
+
+    let tls = &options.tls;
+    if let Some(cert) = &tls.cert {
+        parts.push(CurlArg::option("--cert", "cert", cert.clone()));
+    }
+    if let Some(cert_type) = &tls.cert_type {
+        parts.push(CurlArg::option("--cert-type", "cert-type", cert_type.clone()));
+    }
+    if let Some(key) = &tls.key {
+        parts.push(CurlArg::option("--key", "key", key.clone()));
+    }
+    if let Some(passphrase) = &tls.passphrase {
+        parts.push(CurlArg::option("--pass", "pass", passphrase.clone()));
+    }
+    if let Some(ca_cert) = &tls.ca_cert {
+        parts.push(CurlArg::option("--cacert", "cacert", ca_cert.clone()));
+    }
+    if tls.insecure {
+        parts.push(CurlArg::switch("--insecure", "insecure"));
+    }
+
+    parts.extend(CurlArg::verbatim(&options.extra_args));
+
+    parts
+}
+
+/// Short curl options that take a value.
+const SHORT_WITH_VALUE: &str = "AbcCdDeEFHKmoPQrtTuUwxXyYz";
+
+/// Long curl options that take a value.
+const LONG_WITH_VALUE: &[&str] = &[
+    "abstract-unix-socket", "alt-svc", "aws-sigv4", "cacert", "capath", "cert", "cert-type", "ciphers", "config",
+    "connect-timeout", "connect-to", "continue-at", "cookie", "cookie-jar", "create-file-mode", "crlfile", "curves",
+    "data", "data-ascii", "data-binary", "data-raw", "data-urlencode", "delegation", "dns-interface", "dns-ipv4-addr",
+    "dns-ipv6-addr", "dns-servers", "doh-url", "dump-header", "ech", "egd-file", "engine", "etag-compare",
+    "etag-save", "expect100-timeout", "form", "form-string", "ftp-account", "ftp-alternative-to-user", "ftp-method",
+    "ftp-port", "ftp-ssl-ccc-mode", "happy-eyeballs-timeout-ms", "header", "hostpubmd5", "hostpubsha256", "hsts",
+    "interface", "ip-tos", "json", "keepalive-cnt", "keepalive-time", "key", "key-type", "krb", "libcurl",
+    "limit-rate", "local-port", "login-options", "mail-auth", "mail-from", "mail-rcpt", "max-filesize", "max-redirs",
+    "max-time", "netrc-file", "noproxy", "oauth2-bearer", "output", "output-dir", "parallel-max", "pass",
+    "pinnedpubkey", "preproxy", "proto", "proto-default", "proto-redir", "proxy", "proxy-cacert", "proxy-capath",
+    "proxy-cert", "proxy-cert-type", "proxy-ciphers", "proxy-crlfile", "proxy-header", "proxy-key", "proxy-key-type",
+    "proxy-pass", "proxy-pinnedpubkey", "proxy-service-name", "proxy-tls13-ciphers", "proxy-tlsauthtype",
+    "proxy-tlspassword", "proxy-tlsuser", "proxy-user", "proxy1.0", "pubkey", "quote", "random-file", "range", "rate",
+    "referer", "request", "request-target", "resolve", "retry", "retry-delay", "retry-max-time", "sasl-authzid",
+    "service-name", "socks4", "socks4a", "socks5", "socks5-gssapi-service", "socks5-hostname", "speed-limit",
+    "speed-time", "stderr", "telnet-option", "tftp-blksize", "time-cond", "tls-max", "tls13-ciphers", "tlsauthtype",
+    "tlspassword", "tlsuser", "trace", "trace-ascii", "trace-config", "unix-socket", "upload-file", "url",
+    "url-query", "user", "user-agent", "variable", "vlan-priority", "write-out",
+];
+
+/// Whether the word after `arg` is its value. In a bundle of short options
+/// (`-sSo`) only the last one can take the next word; one earlier in the
+/// bundle takes the rest of the bundle instead (`-ofile`).
+fn takes_value(arg: &str) -> bool {
+    if let Some(long) = arg.strip_prefix("--") {
+        return LONG_WITH_VALUE.contains(&long);
+    }
+    let letters = &arg[1..];
+    match letters.find(|c| SHORT_WITH_VALUE.contains(c)) {
+        Some(index) => index == letters.len() - 1,
+        None => false,
+    }
+}
+
+/// curl sends GET, or POST once there is data, on its own; `-X` is only
+/// needed for anything else. HEAD uses `-I`: with `-X HEAD` curl waits for a
+/// body that never comes.
+fn method_arg(method: Method, sends_data: bool) -> Option<CurlArg> {
+    match method {
+        Method::Head => Some(CurlArg::switch("-I", "head")),
+        Method::Get if !sends_data => None,
+        Method::Post if sends_data => None,
+        method => Some(CurlArg::option("-X", "request", method.as_str().to_string())),
+    }
+}
+
+/// Escapes a value for a double-quoted curl config string.
+fn escape_config(value: &str) -> String {
+    let mut result = String::new();
+    for c in value.chars() {
+        match c {
+            '\\' => result.push_str("\\\\"),
+            '"' => result.push_str("\\\""),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::ast::{Body, Method};
+    use crate::proxy::ProxySettings;
+    use crate::render::tests::make_bru;
+    use crate::shell::Shell;
+    use crate::tls::TlsSettings;
+
+    #[test]
+    fn simple_get_request() {
+        let bru = make_bru(Method::Get, "https://api.example.com");
+        let curl = generate_curl(&bru, &RenderOptions::default());
+        assert_eq!(curl, "curl https://api.example.com");
+    }
+
+    #[test]
+    fn post_with_json_body() {
+        let mut bru = make_bru(Method::Post, "https://api.example.com");
+        bru.body = Some(Body {
+            body_type: "json".to_string(),
+            content: r#"{"name": "John"}"#.to_string(),
+            ..Default::default()
+        });
+        let curl = generate_curl(&bru, &RenderOptions::default());
+        assert!(!curl.contains("-X"));
+        assert!(curl.contains("-H 'Content-Type: application/json'"));
+        assert!(curl.contains(r#"-d '{"name": "John"}'"#));
+    }
+
+    #[test]
+    fn request_with_headers() {
+        let mut bru = make_bru(Method::Get, "https://api.example.com");
+        bru.headers.push(("Authorization".to_string(), "Bearer token".to_string()));
+        let curl = generate_curl(&bru, &RenderOptions::default());
+        assert!(curl.contains("-H 'Authorization: Bearer token'"));
+    }
+
+    #[test]
+    fn verbose_and_silent_flags() {
+        let bru = make_bru(Method::Get, "https://api.example.com");
+
+        let curl_verbose = generate_curl(&bru, &RenderOptions { verbose: true, ..Default::default() });
+        assert!(curl_verbose.contains("-v"));
+
+        let curl_silent = generate_curl(&bru, &RenderOptions { silent: true, ..Default::default() });
+        assert!(curl_silent.contains("-s"));
+    }
+
+    #[test]
+    fn no_duplicate_content_type() {
+        let mut bru = make_bru(Method::Post, "https://api.example.com");
+        bru.headers.push(("Content-Type".to_string(), "text/plain".to_string()));
+        bru.body = Some(Body {
+            body_type: "json".to_string(),
+            content: "test".to_string(),
+            ..Default::default()
+        });
+        let curl = generate_curl(&bru, &RenderOptions::default());
+        let content_type_count = curl.matches("Content-Type").count();
+        assert_eq!(content_type_count, 1);
+    }
+
+    #[test]
+    fn escape_single_quotes_in_body() {
+        let mut bru = make_bru(Method::Post, "https://api.example.com");
+        bru.body = Some(Body {
+            body_type: "json".to_string(),
+            content: r#"{"name": "O'Brien"}"#.to_string(),
+            ..Default::default()
+        });
+        let curl = generate_curl(&bru, &RenderOptions::default());
+        assert!(curl.contains(r"O'\''Brien"));
+    }
+
+    #[test]
+    fn path_params_replace_url_segments() {
+        let mut bru = make_bru(Method::Get, "http://localhost:3000/users/:id/posts?sort=:id");
+        bru.path_params = vec![("id".to_string(), "42".to_string())];
+        let curl = generate_curl(&bru, &RenderOptions::default());
+        assert!(curl.contains("'http://localhost:3000/users/42/posts?sort=:id'"));
+    }
+
+    #[test]
+    fn form_and_multipart_fields() {
+        let mut bru = make_bru(Method::Post, "https://api.example.com");
+        bru.body = Some(Body {
+            body_type: "form-urlencoded".to_string(),
+            fields: vec![("name".to_string(), "John Doe".to_string())],
+            ..Default::default()
+        });
+        let curl = generate_curl(&bru, &RenderOptions::default());
+        assert!(curl.contains("--data-urlencode 'name=John Doe'"));
+
+        bru.body = Some(Body {
+            body_type: "multipart-form".to_string(),
+            fields: vec![
+                ("title".to_string(), "Avatar".to_string()),
+                ("owner".to_string(), "@/etc/passwd".to_string()),
+                ("file".to_string(), "@file(images/a.png)".to_string()),
+            ],
+            ..Default::default()
+        });
+        let curl = generate_curl(&bru, &RenderOptions::default());
+        assert!(curl.contains("--form-string title=Avatar --form-string owner=@/etc/passwd -F file=@images/a.png"));
+        assert!(!curl.contains("Content-Type"));
+    }
+
+    #[test]
+    fn basic_and_bearer_auth() {
+        let mut bru = make_bru(Method::Get, "https://api.example.com");
+        bru.auth = Some(Auth::Basic { username: "bob".to_string(), password: "pw".to_string() });
+        assert!(generate_curl(&bru, &RenderOptions::default()).contains("-u bob:pw"));
+
+        bru.auth = Some(Auth::Bearer { token: "abc".to_string() });
+        assert!(generate_curl(&bru, &RenderOptions::default()).contains("-H 'Authorization: Bearer abc'"));
+    }
+
+    #[test]
+    fn every_argument_survives_the_shell() {
+        let mut bru = make_bru(Method::Post, "http://h/it's?q=$(rm -rf ~)&x=`id`");
+        bru.headers.push(("X-Note".to_string(), "it's \"quoted\"; echo pwned".to_string()));
+        bru.auth = Some(Auth::Basic { username: "o'neil".to_string(), password: "p$ss".to_string() });
+        bru.body = Some(Body {
+            body_type: "text".to_string(),
+            content: "line one\nline 'two'".to_string(),
+            ..Default::default()
+        });
+        let options = RenderOptions::default();
+
+        let curl = generate_curl(&bru, &options);
+        let words = crate::shell::tests::sh_words(curl.strip_prefix("curl ").unwrap());
+        assert_eq!(words, curl_command(&bru, &options)[1..]);
+    }
+
+    #[test]
+    fn powershell_calls_curl_exe() {
+        let mut bru = make_bru(Method::Get, "https://api.example.com/?q=it's");
+        bru.headers.push(("Accept".to_string(), "application/json".to_string()));
+        let options = RenderOptions { shell: Shell::Powershell, ..Default::default() };
+        assert_eq!(
+            generate_curl(&bru, &options),
+            "curl.exe 'https://api.example.com/?q=it''s' -H 'Accept: application/json'"
+        );
+    }
+
+    #[test]
+    fn cmd_line_breaks_are_reported() {
+        let mut bru = make_bru(Method::Post, "https://api.example.com");
+        bru.body = Some(Body { body_type: "text".to_string(), content: "a\nb".to_string(), ..Default::default() });
+        let cmd = RenderOptions { shell: Shell::Cmd, ..Default::default() };
+        assert!(generate_curl(&bru, &cmd).ends_with("-d ^\"a b^\""));
+        assert_eq!(
+            Curl.warnings(&bru, &cmd),
+            vec!["cmd.exe cannot pass line breaks inside an argument; they are replaced with spaces"]
+        );
+        assert!(Curl.warnings(&bru, &RenderOptions::default()).is_empty());
+        assert!(CurlConfig.warnings(&bru, &cmd).is_empty());
+    }
+
+    #[test]
+    fn pretty_output_puts_each_flag_on_its_own_line() {
+        let mut bru = make_bru(Method::Post, "https://api.example.com/users");
+        bru.body = Some(Body {
+            body_type: "json".to_string(),
+            content: r#"{"name": "O'Brien", "tags": ["a"]}"#.to_string(),
+            ..Default::default()
+        });
+        let options = RenderOptions { pretty: true, ..Default::default() };
+
+        let curl = generate_curl(&bru, &options);
+        assert_eq!(
+            curl,
+            "curl \\\n  https://api.example.com/users \\\n  -H 'Content-Type: application/json' \\\n  \
+             -d '{\n  \"name\": \"O'\\''Brien\",\n  \"tags\": [\n    \"a\"\n  ]\n}'"
+        );
+
+        let words = crate::shell::tests::sh_words(curl.strip_prefix("curl ").unwrap());
+        assert_eq!(words, curl_command(&bru, &options)[1..]);
+    }
+
+    #[test]
+    fn curl_config_uses_long_names() {
+        let mut bru = make_bru(Method::Post, "https://api.example.com/users?q=a b");
+        bru.headers.push(("X-Note".to_string(), r#"say "hi""#.to_string()));
+        bru.body = Some(Body {
+            body_type: "json".to_string(),
+            content: "{\n  \"path\": \"C:\\\\tmp\"\n}".to_string(),
+            ..Default::default()
+        });
+        let options = RenderOptions { silent: true, ..Default::default() };
+
+        assert_eq!(
+            generate_curl_config(&bru, &options),
+            [
+                "silent",
+                r#"url = "https://api.example.com/users?q=a b""#,
+                r#"header = "Content-Type: application/json""#,
+                r#"data-raw = "{\n  \"path\": \"C:\\\\tmp\"\n}""#,
+                r#"header = "X-Note: say \"hi\"""#,
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn body_starting_with_at_is_sent_raw() {
+        let mut bru = make_bru(Method::Post, "https://api.example.com");
+        bru.body = Some(Body {
+            body_type: "text".to_string(),
+            content: "@handle".to_string(),
+            ..Default::default()
+        });
+        assert!(generate_curl(&bru, &RenderOptions::default()).contains("--data-raw @handle"));
+    }
+
+    #[test]
+    fn method_flags_only_where_curl_cannot_infer_them() {
+        let json = Body {
+            body_type: "json".to_string(),
+            content: "{}".to_string(),
+            ..Default::default()
+        };
+        let method_of = |method: Method, body: Option<Body>| {
+            let mut bru = make_bru(method, "https://h");
+            bru.body = body;
+            curl_args(&bru, &RenderOptions::default())
+                .into_iter()
+                .find(|arg| arg.name == "request" || arg.name == "head")
+                .map(|arg| arg.words().join(" "))
+        };
+
+        assert_eq!(method_of(Method::Get, None), None);
+        assert_eq!(method_of(Method::Get, Some(json.clone())), Some("-X GET".to_string()));
+        assert_eq!(method_of(Method::Post, Some(json.clone())), None);
+        assert_eq!(method_of(Method::Post, None), Some("-X POST".to_string()));
+        assert_eq!(method_of(Method::Put, Some(json.clone())), Some("-X PUT".to_string()));
+        assert_eq!(method_of(Method::Patch, Some(json.clone())), Some("-X PATCH".to_string()));
+        assert_eq!(method_of(Method::Delete, None), Some("-X DELETE".to_string()));
+        assert_eq!(method_of(Method::Options, None), Some("-X OPTIONS".to_string()));
+        assert_eq!(method_of(Method::Head, None), Some("-I".to_string()));
+    }
+
+    #[test]
+    fn head_requests_never_send_a_body() {
+        let mut bru = make_bru(Method::Head, "https://h");
+        bru.body = Some(Body {
+            body_type: "json".to_string(),
+            content: "{}".to_string(),
+            ..Default::default()
+        });
+        assert_eq!(generate_curl(&bru, &RenderOptions::default()), "curl -I https://h");
+        assert_eq!(generate_curl_config(&bru, &RenderOptions::default()), "head\nurl = \"https://h\"");
+    }
+
+    #[test]
+    fn extra_args_are_appended_and_quoted() {
+        let bru = make_bru(Method::Get, "https://h");
+        let options = RenderOptions {
+            extra_args: ["-o", "out.json", "-i", "-w", "%{http_code} done", "--fail-with-body"]
+                .map(String::from)
+                .to_vec(),
+            ..Default::default()
+        };
+
+        let curl = generate_curl(&bru, &options);
+        assert_eq!(curl, "curl https://h -o out.json -i -w '%{http_code} done' --fail-with-body");
+        assert_eq!(crate::shell::tests::sh_words(curl.strip_prefix("curl ").unwrap()), curl_command(&bru, &options)[1..]);
+
+        let powershell = RenderOptions { shell: Shell::Powershell, ..options };
+        assert!(generate_curl(&bru, &powershell).ends_with("-w '%{http_code} done' --fail-with-body"));
+    }
+
+    #[test]
+    fn extra_args_in_config_and_pretty_output() {
+        let bru = make_bru(Method::Get, "https://h");
+        let options = RenderOptions {
+            extra_args: ["--max-time", "5", "-L"].map(String::from).to_vec(),
+            ..Default::default()
+        };
+        assert_eq!(generate_curl_config(&bru, &options), "url = \"https://h\"\n--max-time \"5\"\n-L");
+
+        let pretty = RenderOptions { pretty: true, ..options };
+        assert_eq!(generate_curl(&bru, &pretty), "curl \\\n  https://h \\\n  --max-time 5 \\\n  -L");
+
+        let options = RenderOptions {
+            extra_args: ["-L", "https://mirror", "-sSo", "out", "-ofile", "--no-progress-meter"].map(String::from).to_vec(),
+            ..Default::default()
+        };
+        assert_eq!(
+            generate_curl_config(&bru, &options),
+            "url = \"https://h\"\n-L\nurl = \"https://mirror\"\n-sSo \"out\"\n-ofile\n--no-progress-meter"
+        );
+    }
+
+    #[test]
+    fn proxy_options() {
+        let bru = make_bru(Method::Get, "https://staging.example.com");
+        let options = RenderOptions {
+            proxy: ProxySettings {
+                url: Some("http://proxy.corp:3128".to_string()),
+                user: Some("bob:pw".to_string()),
+                no_proxy: Some("localhost,*.internal".to_string()),
+            },
+            ..Default::default()
+        };
+        assert_eq!(
+            generate_curl(&bru, &options),
+            "curl https://staging.example.com --proxy http://proxy.corp:3128 --proxy-user bob:pw --noproxy 'localhost,*.internal'"
+        );
+        assert!(generate_curl_config(&bru, &options).contains("\nproxy-user = \"bob:pw\"\nnoproxy = \"localhost,*.internal\""));
+    }
+
+    #[test]
+    fn tls_options() {
+        let bru = make_bru(Method::Get, "https://mtls.example.com");
+        let options = RenderOptions {
+            tls: TlsSettings {
+                cert: Some("/repo/certs/client.pfx".to_string()),
+                cert_type: Some("P12".to_string()),
+                passphrase: Some("p w".to_string()),
+                ca_cert: Some("/repo/ca.pem".to_string()),
+                insecure: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(
+            generate_curl(&bru, &options),
+            "curl https://mtls.example.com --cert /repo/certs/client.pfx --cert-type P12 --pass 'p w' --cacert /repo/ca.pem --insecure"
+        );
+    }
+
+    #[test]
+    fn cookies_and_cookie_jar() {
+        let mut bru = make_bru(Method::Get, "https://h");
+        bru.cookies = vec![("session".to_string(), "abc".to_string()), ("theme".to_string(), "dark".to_string())];
+        let options = RenderOptions { cookie_jar: Some("jar.txt".to_string()), ..Default::default() };
+
+        assert_eq!(
+            generate_curl(&bru, &options),
+            "curl https://h -b 'session=abc; theme=dark' -b jar.txt -c jar.txt"
+        );
+        assert!(generate_curl_config(&bru, &options).ends_with("cookie = \"jar.txt\"\ncookie-jar = \"jar.txt\""));
+    }
+}
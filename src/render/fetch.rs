@@ -0,0 +1,292 @@
+use std::collections::HashSet;
+
+use crate::parser::ast::{Auth, Body, BruFile, Method};
+
+use super::{default_content_type, file_path, has_header, timeout_ms, RenderOptions, Renderer};
+
+const INDENT: &str = "  ";
+
+/// A JavaScript `fetch` snippet, for browsers and Node 18+.
+pub struct Fetch;
+
+impl Renderer for Fetch {
+    fn render(&self, bru: &BruFile, _options: &RenderOptions) -> String {
+        let mut lines = Vec::new();
+        let mut init: Vec<(String, String)> = vec![("method".to_string(), js_str(bru.request.method.as_str()))];
+        let mut headers: Vec<(String, String)> = Vec::new();
+
+        if let Some(body) = bru.body.as_ref().filter(|_| !drops_body(bru)) {
+            if body.body_type == "multipart-form" {
+                lines.extend(form_data(body));
+                init.push(("body".to_string(), "form".to_string()));
+            } else if let Some((expression, raw)) = body_expression(body) {
+                // fetch labels string bodies text/plain, so say what they are.
+                if raw && !has_header(bru, "content-type") {
+                    headers.push(("Content-Type".to_string(), js_str(default_content_type(body))));
+                }
+                init.push(("body".to_string(), expression));
+            }
+        }
+
+        match &bru.auth {
+            Some(Auth::Basic { username, password }) => {
+                // btoa throws on characters outside Latin-1, so encode the UTF-8 here.
+                let credentials = base64(format!("{}:{}", username, password).as_bytes());
+                headers.push(("Authorization".to_string(), js_str(&format!("Basic {}", credentials))));
+            }
+            Some(Auth::Bearer { token }) if !has_header(bru, "authorization") => {
+                headers.push(("Authorization".to_string(), js_str(&format!("Bearer {}", token))));
+            }
+            _ => {}
+        }
+        headers.extend(bru.headers.iter().map(|(name, value)| (name.clone(), js_str(value))));
+
+        if !bru.cookies.is_empty() {
+            let cookies: Vec<String> = bru.cookies.iter().map(|(name, value)| format!("{}={}", name, value)).collect();
+            headers.push(("Cookie".to_string(), js_str(&cookies.join("; "))));
+        }
+
+        if !headers.is_empty() {
+            let headers: Vec<(String, String)> = headers.into_iter().map(|(name, value)| (js_str(&name), value)).collect();
+            init.insert(1, ("headers".to_string(), js_object(&headers)));
+        }
+
+        if let Some(ms) = timeout_ms(bru) {
+            init.push(("signal".to_string(), format!("AbortSignal.timeout({})", ms)));
+        }
+
+        lines.push(format!("const response = await fetch({}, {});", js_str(&bru.resolved_url()), js_object(&init)));
+        lines.push(String::new());
+        lines.push("console.log(response.status);".to_string());
+        lines.push("console.log(await response.text());".to_string());
+        lines.join("\n")
+    }
+
+    fn warnings(&self, bru: &BruFile, options: &RenderOptions) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if bru.body.is_some() && drops_body(bru) {
+            warnings.push(format!("fetch cannot send a body with {}; it is left out", bru.request.method.as_str()));
+        }
+        if options.proxy.url.is_some() || options.proxy.no_proxy.is_some() {
+            warnings.push("fetch has no proxy option; the proxy settings are left out".to_string());
+        }
+        let tls = &options.tls;
+        if tls.cert.is_some() || tls.ca_cert.is_some() || tls.insecure {
+            warnings.push("fetch has no TLS options; the certificate and verification settings are left out".to_string());
+        }
+        if !bru.cookies.is_empty() || has_header(bru, "cookie") {
+            warnings.push("browsers do not let fetch set a Cookie header; it only reaches the server from Node".to_string());
+        }
+        if options.cookie_jar.is_some() {
+            warnings.push("fetch has no cookie jar file; --cookie-jar is left out".to_string());
+        }
+        if !options.extra_args.is_empty() {
+            warnings.push(format!("extra arguments are left out: {}", options.extra_args.join(" ")));
+        }
+        warnings
+    }
+}
+
+/// fetch rejects a GET or HEAD request that has a body.
+fn drops_body(bru: &BruFile) -> bool {
+    matches!(bru.request.method, Method::Get | Method::Head)
+}
+
+/// The `body` expression, and whether it is a string needing a Content-Type.
+fn body_expression(body: &Body) -> Option<(String, bool)> {
+    match body.body_type.as_str() {
+        "form-urlencoded" => {
+            let fields: Vec<(String, String)> = body.fields.iter().map(|(name, value)| (js_str(name), js_str(value))).collect();
+            Some((format!("new URLSearchParams({})", js_pairs(&fields)), false))
+        }
+        _ if body.content.is_empty() => None,
+        _ => Some((js_str(&body.content), true)),
+    }
+}
+
+/// Statements that build `form`, reading `@file(...)` fields from disk.
+fn form_data(body: &Body) -> Vec<String> {
+    let mut lines = Vec::new();
+    let has_files = body.fields.iter().any(|(_, value)| file_path(value).is_some());
+    if has_files {
+        lines.push("import { readFile } from \"node:fs/promises\";".to_string());
+        lines.push(String::new());
+    }
+
+    lines.push("const form = new FormData();".to_string());
+    for (name, value) in &body.fields {
+        let line = match file_path(value) {
+            Some(path) => {
+                let file_name = path.rsplit(['/', '\\']).next().unwrap_or(path);
+                format!(
+                    "form.append({}, new Blob([await readFile({})]), {});",
+                    js_str(name),
+                    js_str(path),
+                    js_str(file_name)
+                )
+            }
+            None => format!("form.append({}, {});", js_str(name), js_str(value)),
+        };
+        lines.push(line);
+    }
+    lines.push(String::new());
+    lines
+}
+
+/// An object literal, one property per line.
+fn js_object(entries: &[(String, String)]) -> String {
+    let items: Vec<String> = entries
+        .iter()
+        .map(|(key, value)| format!("{}{}: {},", INDENT, key, value.replace('\n', &format!("\n{}", INDENT))))
+        .collect();
+    format!("{{\n{}\n}}", items.join("\n"))
+}
+
+/// An object literal, or an array of pairs when a key repeats.
+fn js_pairs(entries: &[(String, String)]) -> String {
+    let mut seen = HashSet::new();
+    if entries.iter().all(|(key, _)| seen.insert(key)) {
+        return js_object(entries);
+    }
+    let items: Vec<String> = entries.iter().map(|(key, value)| format!("{}[{}, {}],", INDENT, key, value)).collect();
+    format!("[\n{}\n]", items.join("\n"))
+}
+
+/// Standard base64 with padding.
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::new();
+    for chunk in bytes.chunks(3) {
+        let n = chunk.iter().enumerate().fold(0u32, |n, (i, &b)| n | ((b as u32) << (16 - 8 * i)));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[((n >> (18 - 6 * i)) & 63) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+/// A double-quoted JavaScript string literal.
+fn js_str(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proxy::ProxySettings;
+    use crate::render::tests::{make_bru, render, with_body};
+    use crate::tls::TlsSettings;
+
+    #[test]
+    fn simple_get() {
+        let bru = make_bru(Method::Get, "https://h/users?page=2");
+        assert_eq!(
+            render(&Fetch, &bru),
+            "const response = await fetch(\"https://h/users?page=2\", {\n  method: \"GET\",\n});\n\n\
+             console.log(response.status);\nconsole.log(await response.text());"
+        );
+    }
+
+    #[test]
+    fn json_body_is_sent_as_written() {
+        let json = r#"{"id": 12345678901234567890, "n": 1.0, "__proto__": {}}"#;
+        let mut bru = with_body(make_bru(Method::Post, "https://h"), "json", json);
+        bru.auth = Some(Auth::Bearer { token: "t0k".to_string() });
+        let js = render(&Fetch, &bru);
+        assert!(js.contains(
+            "  headers: {\n    \"Content-Type\": \"application/json\",\n    \"Authorization\": \"Bearer t0k\",\n  },\n"
+        ));
+        assert!(js.contains(r#"  body: "{\"id\": 12345678901234567890, \"n\": 1.0, \"__proto__\": {}}","#));
+    }
+
+    #[test]
+    fn get_body_is_dropped_with_a_warning() {
+        let bru = with_body(make_bru(Method::Get, "https://h"), "json", "{}");
+        assert!(!render(&Fetch, &bru).contains("body"));
+        assert_eq!(
+            Fetch.warnings(&bru, &RenderOptions::default()),
+            vec!["fetch cannot send a body with GET; it is left out"]
+        );
+    }
+
+    #[test]
+    fn form_bodies_use_url_search_params() {
+        let mut form = with_body(make_bru(Method::Post, "https://h"), "form-urlencoded", "");
+        form.body.as_mut().unwrap().fields = vec![("q".to_string(), "a b".to_string())];
+        let js = render(&Fetch, &form);
+        assert!(js.contains("  body: new URLSearchParams({\n    \"q\": \"a b\",\n  }),\n"));
+        assert!(!js.contains("headers"));
+
+        form.body.as_mut().unwrap().fields.push(("q".to_string(), "c".to_string()));
+        assert!(render(&Fetch, &form).contains("new URLSearchParams([\n    [\"q\", \"a b\"],\n    [\"q\", \"c\"],\n  ])"));
+    }
+
+    #[test]
+    fn multipart_builds_form_data() {
+        let mut bru = with_body(make_bru(Method::Put, "https://h"), "multipart-form", "");
+        bru.body.as_mut().unwrap().fields =
+            vec![("title".to_string(), "x".to_string()), ("doc".to_string(), "@file(docs/a.pdf)".to_string())];
+        let js = render(&Fetch, &bru);
+        assert!(js.starts_with(
+            "import { readFile } from \"node:fs/promises\";\n\n\
+             const form = new FormData();\n\
+             form.append(\"title\", \"x\");\n\
+             form.append(\"doc\", new Blob([await readFile(\"docs/a.pdf\")]), \"a.pdf\");\n\n"
+        ));
+        assert!(js.contains("  method: \"PUT\",\n  body: form,\n"));
+    }
+
+    #[test]
+    fn basic_auth_cookies_and_timeout() {
+        let mut bru = with_body(make_bru(Method::Head, "https://h"), "text", "ignored");
+        bru.auth = Some(Auth::Basic { username: "u".to_string(), password: "p\"w".to_string() });
+        bru.cookies = vec![("sid".to_string(), "abc".to_string())];
+        bru.settings.insert("timeout".to_string(), "5000".to_string());
+        let js = render(&Fetch, &bru);
+        assert!(js.contains("    \"Authorization\": \"Basic dTpwInc=\",\n"));
+        assert!(js.contains("    \"Cookie\": \"sid=abc\",\n"));
+        assert!(js.contains("  signal: AbortSignal.timeout(5000),\n"));
+        assert!(!js.contains("ignored"));
+    }
+
+    #[test]
+    fn unsupported_settings_are_reported() {
+        let bru = make_bru(Method::Post, "https://h");
+        let options = RenderOptions {
+            proxy: ProxySettings { no_proxy: Some("*".to_string()), ..Default::default() },
+            tls: TlsSettings { insecure: true, ..Default::default() },
+            cookie_jar: Some("jar.txt".to_string()),
+            extra_args: vec!["-L".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(Fetch.warnings(&bru, &options).len(), 4);
+        assert!(Fetch.warnings(&bru, &RenderOptions::default()).is_empty());
+    }
+
+    #[test]
+    fn cookies_are_reported() {
+        let mut bru = make_bru(Method::Get, "https://h");
+        bru.headers.push(("cookie".to_string(), "a=1".to_string()));
+        assert_eq!(
+            Fetch.warnings(&bru, &RenderOptions::default()),
+            vec!["browsers do not let fetch set a Cookie header; it only reaches the server from Node"]
+        );
+
+        let mut bru = make_bru(Method::Get, "https://h");
+        bru.cookies = vec![("sid".to_string(), "abc".to_string())];
+        assert_eq!(Fetch.warnings(&bru, &RenderOptions::default()).len(), 1);
+    }
+
+    #[test]
+    fn base64_encodes_utf8() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64("u:pé".as_bytes()), "dTpww6k=");
+    }
+}
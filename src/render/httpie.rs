@@ -0,0 +1,369 @@
+use crate::json::{object_members, pretty_print};
+use crate::parser::ast::{Auth, Body, BruFile, Method};
+
+use super::{default_content_type, file_path, has_header, proxy_url, RenderOptions, Renderer};
+
+/// An HTTPie command line: `http POST url Header:value field=value`.
+pub struct Httpie;
+
+impl Renderer for Httpie {
+    fn render(&self, bru: &BruFile, options: &RenderOptions) -> String {
+        let program = vec!["http".to_string()];
+        let groups = httpie_args(bru, options);
+
+        if options.pretty {
+            let lines: Vec<Vec<String>> = std::iter::once(program).chain(groups).collect();
+            options.shell.join_lines(&lines)
+        } else {
+            let words: Vec<String> = program.into_iter().chain(groups.into_iter().flatten()).collect();
+            options.shell.join(&words)
+        }
+    }
+
+    fn warnings(&self, bru: &BruFile, options: &RenderOptions) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if let Some(cert) = options.tls.cert.as_ref().filter(|_| options.tls.is_pfx()) {
+            warnings.push(format!("HTTPie cannot use the PFX client certificate {}; it is left out", cert));
+        }
+        if let Some(hosts) = &options.proxy.no_proxy {
+            warnings.push(format!("the proxy bypass list '{}' is left out", hosts));
+        }
+        if !options.extra_args.is_empty() {
+            warnings.push(format!("extra arguments are left out: {}", options.extra_args.join(" ")));
+        }
+
+        let fields = bru
+            .body
+            .iter()
+            .filter(|body| matches!(body.body_type.as_str(), "multipart-form" | "form-urlencoded"))
+            .flat_map(|body| &body.fields);
+        for (name, value) in bru.headers.iter().chain(fields) {
+            if !is_exact(name) || !is_exact(value) {
+                warnings.push(format!("HTTPie drops a backslash before {} in '{}'", SEPARATORS, name));
+            }
+        }
+
+        let words: Vec<String> = httpie_args(bru, options).into_iter().flatten().collect();
+        warnings.extend(options.shell.line_break_warning(&words));
+        warnings
+    }
+}
+
+/// The arguments HTTPie needs, grouped so that a flag stays with its value.
+/// Flags come first, then the method and URL, then the request items.
+pub fn httpie_args(bru: &BruFile, options: &RenderOptions) -> Vec<Vec<String>> {
+    let mut flags: Vec<Vec<String>> = Vec::new();
+    let mut items: Vec<Vec<String>> = Vec::new();
+
+    if options.verbose {
+        flags.push(vec!["--verbose".to_string()]);
+    }
+
+    let is_head = matches!(bru.request.method, Method::Head);
+    if let Some(body) = bru.body.as_ref().filter(|_| !is_head) {
+        body_args(bru, body, options, &mut flags, &mut items);
+    }
+
+    match &bru.auth {
+        Some(Auth::Basic { username, password }) => {
+            flags.push(vec!["-a".to_string(), format!("{}:{}", username, password)]);
+        }
+        Some(Auth::Bearer { token }) if !has_header(bru, "authorization") => {
+            flags.push(vec!["-A".to_string(), "bearer".to_string(), "-a".to_string(), token.clone()]);
+        }
+        _ => {}
+    }
+
+    if let Some(jar) = &options.cookie_jar {
+        flags.push(vec!["--session".to_string(), jar.clone()]);
+    }
+
+    if let Some(url) = proxy_url(&options.proxy) {
+        for scheme in ["http", "https"] {
+            flags.push(vec!["--proxy".to_string(), format!("{}:{}", scheme, url)]);
+        }
+    }
+
+    let tls = &options.tls;
+    if !tls.is_pfx() {
+        if let Some(cert) = &tls.cert {
+            flags.push(vec!["--cert".to_string(), cert.clone()]);
+        }
+        if let Some(key) = &tls.key {
+            flags.push(vec!["--cert-key".to_string(), key.clone()]);
+        }
+        if let Some(passphrase) = &tls.passphrase {
+            flags.push(vec!["--cert-key-pass".to_string(), passphrase.clone()]);
+        }
+    }
+    if tls.insecure {
+        flags.push(vec!["--verify".to_string(), "no".to_string()]);
+    } else if let Some(ca_cert) = &tls.ca_cert {
+        flags.push(vec!["--verify".to_string(), ca_cert.clone()]);
+    }
+
+    for (key, value) in &bru.headers {
+        items.push(vec![header_item(key, value)]);
+    }
+
+    if !bru.cookies.is_empty() {
+        let cookies: Vec<String> = bru.cookies.iter().map(|(name, value)| format!("{}={}", name, value)).collect();
+        items.push(vec![header_item("Cookie", &cookies.join("; "))]);
+    }
+
+    let request = vec![bru.request.method.as_str().to_string(), bru.resolved_url()];
+
+    flags.into_iter().chain(std::iter::once(request)).chain(items).collect()
+}
+
+fn body_args(
+    bru: &BruFile,
+    body: &Body,
+    options: &RenderOptions,
+    flags: &mut Vec<Vec<String>>,
+    items: &mut Vec<Vec<String>>,
+) {
+    match body.body_type.as_str() {
+        "multipart-form" => {
+            flags.push(vec!["--multipart".to_string()]);
+            for (name, value) in &body.fields {
+                let item = match file_path(value) {
+                    Some(path) => item(name, "@", path),
+                    None => item(name, "=", value),
+                };
+                items.push(vec![item]);
+            }
+        }
+        "form-urlencoded" => {
+            flags.push(vec!["--form".to_string()]);
+            for (name, value) in &body.fields {
+                items.push(vec![item(name, "=", value)]);
+            }
+        }
+        _ if body.content.is_empty() => {}
+        body_type => {
+            let is_json = body_type == "json";
+            if is_json && let Some(json_items) = json_items(&body.content) {
+                items.extend(json_items.into_iter().map(|item| vec![item]));
+                return;
+            }
+
+            let content = match body_type {
+                "json" if options.pretty => pretty_print(&body.content).unwrap_or_else(|| body.content.clone()),
+                _ => body.content.clone(),
+            };
+            flags.push(vec!["--raw".to_string(), content]);
+
+            // HTTPie labels raw data as JSON unless told otherwise.
+            if !is_json && !has_header(bru, "content-type") {
+                items.push(vec![header_item("Content-Type", default_content_type(body))]);
+            }
+        }
+    }
+}
+
+/// Turns a flat JSON object into `key=string` and `key:=json` items. Returns
+/// `None` when the body has to go through `--raw` instead: it is not an
+/// object, is empty, has keys HTTPie would read as nested paths, or has a
+/// string an item cannot carry exactly.
+fn json_items(content: &str) -> Option<Vec<String>> {
+    let members = object_members(content).filter(|members| !members.is_empty())?;
+    let plain_key = |key: &str| !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || "_-.".contains(c));
+
+    members
+        .into_iter()
+        .map(|(key, value)| {
+            if !plain_key(&key) {
+                return None;
+            }
+            match serde_json::from_str::<String>(&value) {
+                Ok(text) if is_exact(&text) => Some(item(&key, "=", &text)),
+                Ok(_) => None,
+                Err(_) => Some(format!("{}:={}", key, value)),
+            }
+        })
+        .collect()
+}
+
+/// `Name:value`; HTTPie drops a header written `Name:`, so an empty value
+/// uses its `Name;` form.
+fn header_item(name: &str, value: &str) -> String {
+    if value.is_empty() {
+        format!("{};", name)
+    } else {
+        item(name, ":", value)
+    }
+}
+
+/// Characters HTTPie reads as separators in a request item.
+const SEPARATORS: &str = ":=@;";
+
+/// One request item. HTTPie splits an item at the first separator and takes
+/// the longest one there (`a==5` is a query parameter), so separator
+/// characters in the name and at the start of the value are escaped.
+fn item(name: &str, separator: &str, value: &str) -> String {
+    let mut result = String::new();
+    for c in name.chars() {
+        if SEPARATORS.contains(c) {
+            result.push('\\');
+        }
+        result.push(c);
+    }
+    result.push_str(separator);
+    if value.starts_with(|c| SEPARATORS.contains(c)) {
+        result.push('\\');
+    }
+    result.push_str(value);
+    result
+}
+
+/// Whether HTTPie reads `text` back unchanged from an item: it drops a
+/// backslash in front of a separator character.
+fn is_exact(text: &str) -> bool {
+    !text.as_bytes().windows(2).any(|pair| pair[0] == b'\\' && SEPARATORS.as_bytes().contains(&pair[1]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::tests::{make_bru, render, with_body};
+    use crate::proxy::ProxySettings;
+    use crate::shell::tests::sh_words;
+    use crate::shell::Shell;
+    use crate::tls::TlsSettings;
+
+    #[test]
+    fn get_with_headers() {
+        let mut bru = make_bru(Method::Get, "https://api.example.com/users?page=2");
+        bru.headers.push(("Accept".to_string(), "application/json".to_string()));
+        assert_eq!(
+            render(&Httpie, &bru),
+            "http GET 'https://api.example.com/users?page=2' Accept:application/json"
+        );
+
+        bru.headers.push(("Accept".to_string(), String::new()));
+        assert!(render(&Httpie, &bru).ends_with(" 'Accept;'"));
+    }
+
+    #[test]
+    fn flat_json_becomes_items() {
+        let bru = with_body(
+            make_bru(Method::Post, "https://api.example.com/users"),
+            "json",
+            r#"{"name": "John Smith", "age": 29, "tags": ["a", "b"], "admin": false}"#,
+        );
+        let words = sh_words(&render(&Httpie, &bru));
+        assert_eq!(
+            words,
+            vec!["http", "POST", "https://api.example.com/users", "name=John Smith", "age:=29", r#"tags:=["a","b"]"#, "admin:=false"]
+        );
+    }
+
+    #[test]
+    fn separators_in_values_are_escaped() {
+        let json = r#"{"eq": "=5", "file": "@/etc/passwd", "url": "a:b"}"#;
+        let bru = with_body(make_bru(Method::Post, "https://h"), "json", json);
+        assert_eq!(
+            sh_words(&render(&Httpie, &bru)),
+            vec!["http", "POST", "https://h", r"eq=\=5", r"file=\@/etc/passwd", "url=a:b"]
+        );
+
+        let backslash = with_body(make_bru(Method::Post, "https://h"), "json", r#"{"re": "a\\=b"}"#);
+        assert_eq!(sh_words(&render(&Httpie, &backslash))[1], "--raw");
+
+        let mut form = with_body(make_bru(Method::Post, "https://h"), "multipart-form", "");
+        form.body.as_mut().unwrap().fields =
+            vec![("a:b".to_string(), ":x".to_string()), ("c".to_string(), "@file(d=e)".to_string())];
+        form.headers.push(("X-Eq".to_string(), "=1".to_string()));
+        assert_eq!(
+            sh_words(&render(&Httpie, &form)),
+            vec!["http", "--multipart", "POST", "https://h", r"a\:b=\:x", "c@d=e", r"X-Eq:\=1"]
+        );
+        assert!(Httpie.warnings(&form, &RenderOptions::default()).is_empty());
+
+        form.body.as_mut().unwrap().fields[0].1 = r"x\=y".to_string();
+        assert_eq!(Httpie.warnings(&form, &RenderOptions::default()).len(), 1);
+    }
+
+    #[test]
+    fn cmd_line_breaks_are_reported() {
+        let bru = with_body(make_bru(Method::Post, "https://h"), "text", "a\nb");
+        let cmd = RenderOptions { shell: Shell::Cmd, ..Default::default() };
+        assert_eq!(Httpie.warnings(&bru, &cmd).len(), 1);
+        assert!(Httpie.warnings(&bru, &RenderOptions::default()).is_empty());
+    }
+
+    #[test]
+    fn other_bodies_use_raw() {
+        let nested_key = with_body(make_bru(Method::Put, "https://h"), "json", r#"{"a[0]": 1}"#);
+        assert_eq!(sh_words(&render(&Httpie, &nested_key)), vec!["http", "--raw", r#"{"a[0]": 1}"#, "PUT", "https://h"]);
+
+        let array = with_body(make_bru(Method::Post, "https://h"), "json", r#"[1,2]"#);
+        let pretty = Httpie.render(&array, &RenderOptions { pretty: true, ..Default::default() });
+        assert_eq!(pretty, "http \\\n  --raw '[\n  1,\n  2\n]' \\\n  POST https://h");
+
+        let xml = with_body(make_bru(Method::Post, "https://h"), "xml", "<a/>");
+        assert_eq!(
+            sh_words(&render(&Httpie, &xml)),
+            vec!["http", "--raw", "<a/>", "POST", "https://h", "Content-Type:application/xml"]
+        );
+    }
+
+    #[test]
+    fn forms_and_files() {
+        let mut form = with_body(make_bru(Method::Post, "https://h"), "form-urlencoded", "");
+        form.body.as_mut().unwrap().fields = vec![("q".to_string(), "a b".to_string())];
+        assert_eq!(sh_words(&render(&Httpie, &form)), vec!["http", "--form", "POST", "https://h", "q=a b"]);
+
+        let mut multipart = with_body(make_bru(Method::Post, "https://h"), "multipart-form", "");
+        multipart.body.as_mut().unwrap().fields =
+            vec![("title".to_string(), "x".to_string()), ("file".to_string(), "@file(docs/a.pdf)".to_string())];
+        assert_eq!(
+            sh_words(&render(&Httpie, &multipart)),
+            vec!["http", "--multipart", "POST", "https://h", "title=x", "file@docs/a.pdf"]
+        );
+    }
+
+    #[test]
+    fn auth_and_connection_options() {
+        let mut bru = make_bru(Method::Head, "https://h");
+        bru.auth = Some(Auth::Bearer { token: "t0k".to_string() });
+        bru.cookies = vec![("a".to_string(), "1".to_string()), ("b".to_string(), "2".to_string())];
+        let options = RenderOptions {
+            verbose: true,
+            cookie_jar: Some("session.json".to_string()),
+            proxy: ProxySettings { url: Some("proxy:3128".to_string()), user: Some("bob:pw".to_string()), no_proxy: None },
+            tls: TlsSettings { cert: Some("c.pem".to_string()), ca_cert: Some("ca.pem".to_string()), ..Default::default() },
+            extra_args: vec!["--timeout".to_string(), "5".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            sh_words(&Httpie.render(&bru, &options)),
+            vec![
+                "http", "--verbose", "-A", "bearer", "-a", "t0k", "--session", "session.json",
+                "--proxy", "http:http://bob:pw@proxy:3128", "--proxy", "https:http://bob:pw@proxy:3128",
+                "--cert", "c.pem", "--verify", "ca.pem", "HEAD", "https://h", "Cookie:a=1; b=2",
+            ]
+        );
+        assert_eq!(Httpie.warnings(&bru, &options), vec!["extra arguments are left out: --timeout 5"]);
+    }
+
+    #[test]
+    fn pfx_certificates_are_left_out() {
+        let bru = make_bru(Method::Get, "https://h");
+        let options = RenderOptions {
+            tls: TlsSettings {
+                cert: Some("c.pfx".to_string()),
+                cert_type: Some("P12".to_string()),
+                passphrase: Some("pw".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(Httpie.render(&bru, &options), "http GET https://h");
+        assert_eq!(
+            Httpie.warnings(&bru, &options),
+            vec!["HTTPie cannot use the PFX client certificate c.pfx; it is left out"]
+        );
+    }
+}
@@ -0,0 +1,305 @@
+use std::collections::HashSet;
+
+use crate::json::{pretty_print_indented, rewrite_tokens};
+use crate::parser::ast::{Auth, Body, BruFile, Method};
+
+use super::{default_content_type, file_path, has_header, proxy_url, split_query, timeout_ms, RenderOptions, Renderer};
+
+const INDENT: &str = "    ";
+
+/// A Python snippet using `requests`.
+pub struct Python;
+
+impl Renderer for Python {
+    fn render(&self, bru: &BruFile, options: &RenderOptions) -> String {
+        let mut lines = vec![
+            "import requests".to_string(),
+            String::new(),
+            format!("response = requests.{}(", bru.request.method.as_str().to_lowercase()),
+        ];
+        for arg in call_args(bru, options) {
+            lines.push(format!("{}{},", INDENT, arg.replace('\n', &format!("\n{}", INDENT))));
+        }
+        lines.push(")".to_string());
+        lines.push(String::new());
+        lines.push("print(response.status_code)".to_string());
+        lines.push("print(response.text)".to_string());
+        lines.join("\n")
+    }
+
+    fn warnings(&self, _bru: &BruFile, options: &RenderOptions) -> Vec<String> {
+        let mut warnings = Vec::new();
+        let tls = &options.tls;
+        if let Some(cert) = tls.cert.as_ref().filter(|_| tls.is_pfx()) {
+            warnings.push(format!("requests cannot use the PFX client certificate {}; it is left out", cert));
+        } else if tls.passphrase.is_some() {
+            warnings.push("requests has no option for the client key passphrase; it is left out".to_string());
+        }
+        if let Some(hosts) = &options.proxy.no_proxy {
+            warnings.push(format!("the proxy bypass list '{}' is left out", hosts));
+        }
+        if options.cookie_jar.is_some() {
+            warnings.push("requests has no cookie jar file; --cookie-jar is left out".to_string());
+        }
+        if !options.extra_args.is_empty() {
+            warnings.push(format!("extra arguments are left out: {}", options.extra_args.join(" ")));
+        }
+        warnings
+    }
+}
+
+/// The arguments of the `requests.<method>()` call.
+fn call_args(bru: &BruFile, options: &RenderOptions) -> Vec<String> {
+    let (url, params) = split_query(&bru.resolved_url());
+    let mut args = vec![py_str(&url)];
+
+    if !params.is_empty() {
+        let params: Vec<(String, String)> = params.iter().map(|(name, value)| (name.clone(), py_str(value))).collect();
+        args.push(format!("params={}", py_mapping(&params)));
+    }
+
+    let mut headers: Vec<(String, String)> = Vec::new();
+    let mut body_arg = None;
+
+    let is_head = matches!(bru.request.method, Method::Head);
+    if let Some(body) = bru.body.as_ref().filter(|_| !is_head)
+        && let Some((arg, raw)) = body_kwarg(body)
+    {
+        body_arg = Some(arg);
+        // Only raw `data=` needs a Content-Type; requests sets it for the rest.
+        if raw && !has_header(bru, "content-type") {
+            headers.push(("Content-Type".to_string(), default_content_type(body).to_string()));
+        }
+    }
+
+    if let Some(Auth::Bearer { token }) = &bru.auth
+        && !has_header(bru, "authorization")
+    {
+        headers.push(("Authorization".to_string(), format!("Bearer {}", token)));
+    }
+    headers.extend(bru.headers.iter().map(|(name, value)| (name.clone(), value.clone())));
+
+    if !headers.is_empty() {
+        let headers: Vec<(String, String)> = headers.iter().map(|(name, value)| (name.clone(), py_str(value))).collect();
+        args.push(format!("headers={}", py_mapping(&headers)));
+    }
+
+    if !bru.cookies.is_empty() {
+        let cookies: Vec<(String, String)> = bru.cookies.iter().map(|(name, value)| (name.clone(), py_str(value))).collect();
+        args.push(format!("cookies={}", py_mapping(&cookies)));
+    }
+
+    args.extend(body_arg);
+
+    if let Some(Auth::Basic { username, password }) = &bru.auth {
+        args.push(format!("auth=({}, {})", py_str(username), py_str(password)));
+    }
+
+    if let Some(url) = proxy_url(&options.proxy) {
+        let proxies = vec![("http".to_string(), py_str(&url)), ("https".to_string(), py_str(&url))];
+        args.push(format!("proxies={}", py_mapping(&proxies)));
+    }
+
+    let tls = &options.tls;
+    match (&tls.cert, &tls.key) {
+        _ if tls.is_pfx() => {}
+        (Some(cert), Some(key)) => args.push(format!("cert=({}, {})", py_str(cert), py_str(key))),
+        (Some(cert), None) => args.push(format!("cert={}", py_str(cert))),
+        _ => {}
+    }
+    if tls.insecure {
+        args.push("verify=False".to_string());
+    } else if let Some(ca_cert) = &tls.ca_cert {
+        args.push(format!("verify={}", py_str(ca_cert)));
+    }
+
+    if let Some(ms) = timeout_ms(bru) {
+        args.push(format!("timeout={}", ms as f64 / 1000.0));
+    }
+
+    args
+}
+
+/// The body argument, and whether it is raw text needing a Content-Type.
+fn body_kwarg(body: &Body) -> Option<(String, bool)> {
+    match body.body_type.as_str() {
+        "multipart-form" => {
+            let files: Vec<(String, String)> = body
+                .fields
+                .iter()
+                .map(|(name, value)| {
+                    let value = match file_path(value) {
+                        Some(path) => format!("open({}, \"rb\")", py_str(path)),
+                        None => format!("(None, {})", py_str(value)),
+                    };
+                    (name.clone(), value)
+                })
+                .collect();
+            Some((format!("files={}", py_mapping(&files)), false))
+        }
+        "form-urlencoded" => {
+            let fields: Vec<(String, String)> = body.fields.iter().map(|(name, value)| (name.clone(), py_str(value))).collect();
+            Some((format!("data={}", py_mapping(&fields)), false))
+        }
+        _ if body.content.is_empty() => None,
+        "json" => {
+            let json = pretty_print_indented(&body.content, INDENT).and_then(|json| rewrite_tokens(&json, py_str, py_literal));
+            match json {
+                Some(json) => Some((format!("json={}", json), false)),
+                None => Some((format!("data={}", py_str(&body.content)), true)),
+            }
+        }
+        _ => Some((format!("data={}", py_str(&body.content)), true)),
+    }
+}
+
+/// A dict literal, or a list of pairs when a key repeats.
+fn py_mapping(entries: &[(String, String)]) -> String {
+    let mut seen = HashSet::new();
+    let unique = entries.iter().all(|(key, _)| seen.insert(key));
+
+    let items: Vec<String> = entries
+        .iter()
+        .map(|(key, value)| {
+            if unique {
+                format!("{}{}: {},", INDENT, py_str(key), value)
+            } else {
+                format!("{}({}, {}),", INDENT, py_str(key), value)
+            }
+        })
+        .collect();
+
+    let (open, close) = if unique { ('{', '}') } else { ('[', ']') };
+    format!("{}\n{}\n{}", open, items.join("\n"), close)
+}
+
+fn py_literal(literal: &str) -> String {
+    match literal {
+        "true" => "True".to_string(),
+        "false" => "False".to_string(),
+        "null" => "None".to_string(),
+        number => number.to_string(),
+    }
+}
+
+/// A double-quoted Python string literal.
+fn py_str(value: &str) -> String {
+    let mut result = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '\\' => result.push_str("\\\\"),
+            '"' => result.push_str("\\\""),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if c.is_control() => result.push_str(&format!("\\x{:02x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::tests::{make_bru, render, with_body};
+    use crate::proxy::ProxySettings;
+    use crate::tls::TlsSettings;
+
+    #[test]
+    fn get_with_params_and_headers() {
+        let mut bru = make_bru(Method::Get, "https://h/users?page=2&q=a%20b");
+        bru.headers.push(("Accept".to_string(), "application/json".to_string()));
+        assert_eq!(
+            render(&Python, &bru),
+            "import requests\n\n\
+             response = requests.get(\n    \"https://h/users\",\n    params={\n        \"page\": \"2\",\n        \"q\": \"a b\",\n    },\n    \
+             headers={\n        \"Accept\": \"application/json\",\n    },\n)\n\n\
+             print(response.status_code)\nprint(response.text)"
+        );
+    }
+
+    #[test]
+    fn json_body_becomes_python_literal() {
+        let bru = with_body(make_bru(Method::Post, "https://h"), "json", r#"{"name": "Jo \"J\"", "admin": false, "boss": null, "ids": [1]}"#);
+        let python = render(&Python, &bru);
+        assert!(python.contains(
+            "    json={\n        \"name\": \"Jo \\\"J\\\"\",\n        \"admin\": False,\n        \"boss\": None,\n        \"ids\": [\n            1\n        ]\n    },\n"
+        ));
+        assert!(!python.contains("headers="));
+
+        let invalid = with_body(make_bru(Method::Post, "https://h"), "json", "{{BODY}}");
+        let python = render(&Python, &invalid);
+        assert!(python.contains("    data=\"{{BODY}}\",\n"));
+        assert!(python.contains("\"Content-Type\": \"application/json\""));
+    }
+
+    #[test]
+    fn forms_files_and_raw_text() {
+        let mut form = with_body(make_bru(Method::Post, "https://h"), "form-urlencoded", "");
+        form.body.as_mut().unwrap().fields = vec![("a".to_string(), "1".to_string()), ("a".to_string(), "2".to_string())];
+        assert!(render(&Python, &form).contains("    data=[\n        (\"a\", \"1\"),\n        (\"a\", \"2\"),\n    ],\n"));
+
+        let mut multipart = with_body(make_bru(Method::Put, "https://h"), "multipart-form", "");
+        multipart.body.as_mut().unwrap().fields =
+            vec![("title".to_string(), "x".to_string()), ("doc".to_string(), "@file(a.pdf)".to_string())];
+        let python = render(&Python, &multipart);
+        assert!(python.starts_with("import requests\n\nresponse = requests.put("));
+        assert!(python.contains("        \"title\": (None, \"x\"),\n        \"doc\": open(\"a.pdf\", \"rb\"),\n"));
+
+        let xml = with_body(make_bru(Method::Post, "https://h"), "xml", "<a>\n</a>");
+        let python = render(&Python, &xml);
+        assert!(python.contains("\"Content-Type\": \"application/xml\""));
+        assert!(python.contains("    data=\"<a>\\n</a>\",\n"));
+    }
+
+    #[test]
+    fn auth_cookies_and_connection_settings() {
+        let mut bru = make_bru(Method::Delete, "https://h/x");
+        bru.auth = Some(Auth::Basic { username: "u".to_string(), password: "p\\w".to_string() });
+        bru.cookies = vec![("sid".to_string(), "abc".to_string())];
+        bru.settings.insert("timeout".to_string(), "1500".to_string());
+        let options = RenderOptions {
+            proxy: ProxySettings { url: Some("http://proxy:3128".to_string()), ..Default::default() },
+            tls: TlsSettings { cert: Some("c.pem".to_string()), key: Some("c.key".to_string()), insecure: true, ..Default::default() },
+            ..Default::default()
+        };
+        let python = Python.render(&bru, &options);
+        assert!(python.contains("    cookies={\n        \"sid\": \"abc\",\n    },\n"));
+        assert!(python.contains("    auth=(\"u\", \"p\\\\w\"),\n"));
+        assert!(python.contains("        \"https\": \"http://proxy:3128\",\n"));
+        assert!(python.contains("    cert=(\"c.pem\", \"c.key\"),\n    verify=False,\n    timeout=1.5,\n)"));
+
+        assert!(Python.warnings(&bru, &options).is_empty());
+
+        let mut bearer = make_bru(Method::Get, "https://h");
+        bearer.auth = Some(Auth::Bearer { token: "t".to_string() });
+        assert!(render(&Python, &bearer).contains("\"Authorization\": \"Bearer t\""));
+    }
+
+    #[test]
+    fn pfx_and_unsupported_settings_are_reported() {
+        let bru = make_bru(Method::Get, "https://h");
+        let options = RenderOptions {
+            tls: TlsSettings {
+                cert: Some("c.pfx".to_string()),
+                cert_type: Some("P12".to_string()),
+                passphrase: Some("pw".to_string()),
+                ..Default::default()
+            },
+            cookie_jar: Some("jar.txt".to_string()),
+            extra_args: vec!["-L".to_string()],
+            ..Default::default()
+        };
+        assert!(!Python.render(&bru, &options).contains("cert="));
+        assert_eq!(
+            Python.warnings(&bru, &options),
+            vec![
+                "requests cannot use the PFX client certificate c.pfx; it is left out",
+                "requests has no cookie jar file; --cookie-jar is left out",
+                "extra arguments are left out: -L",
+            ]
+        );
+    }
+}
@@ -0,0 +1,445 @@
+use std::cell::Cell;
+
+use crate::json::{pretty_print_indented, rewrite_tokens};
+use crate::parser::ast::{Auth, Body, BruFile, Method};
+
+use super::{default_content_type, file_path, has_header, proxy_url, split_query, timeout_ms, RenderOptions, Renderer};
+
+const INDENT: &str = "    ";
+
+/// A Rust snippet using async `reqwest`.
+pub struct Reqwest;
+
+impl Renderer for Reqwest {
+    fn render(&self, bru: &BruFile, options: &RenderOptions) -> String {
+        let mut features = Vec::new();
+        let mut body = Vec::new();
+
+        body.extend(client(options));
+        if options.tls.cert.is_some() {
+            features.push(if options.tls.is_pfx() { "native-tls" } else { "rustls-tls" });
+        }
+
+        let is_head = matches!(bru.request.method, Method::Head);
+        let request_body = bru.body.as_ref().filter(|_| !is_head);
+        if let Some(form) = request_body.and_then(multipart_form) {
+            features.push("multipart");
+            body.extend(form);
+        }
+
+        let mut chain = vec!["let response = client".to_string()];
+        let (url, query) = split_query(&bru.resolved_url());
+        chain.push(match bru.request.method {
+            Method::Options => format!(".request(reqwest::Method::OPTIONS, {})", rust_str(&url)),
+            method => format!(".{}({})", method.as_str().to_lowercase(), rust_str(&url)),
+        });
+
+        if !query.is_empty() {
+            chain.push(format!(".query(&{})", rust_pairs(&query)));
+        }
+
+        if let Some(body) = request_body {
+            if body_is_raw(body) && !has_header(bru, "content-type") {
+                chain.push(format!(".header(\"Content-Type\", {})", rust_str(default_content_type(body))));
+            }
+            if let Some(call) = body_call(body) {
+                if call.starts_with(".json(") {
+                    features.push("json");
+                }
+                chain.push(call);
+            }
+        }
+
+        match &bru.auth {
+            Some(Auth::Basic { username, password }) => {
+                chain.push(format!(".basic_auth({}, Some({}))", rust_str(username), rust_str(password)));
+            }
+            Some(Auth::Bearer { token }) if !has_header(bru, "authorization") => {
+                chain.push(format!(".bearer_auth({})", rust_str(token)));
+            }
+            _ => {}
+        }
+
+        for (name, value) in &bru.headers {
+            chain.push(format!(".header({}, {})", rust_str(name), rust_str(value)));
+        }
+
+        if !bru.cookies.is_empty() {
+            let cookies: Vec<String> = bru.cookies.iter().map(|(name, value)| format!("{}={}", name, value)).collect();
+            chain.push(format!(".header(\"Cookie\", {})", rust_str(&cookies.join("; "))));
+        }
+
+        if let Some(ms) = timeout_ms(bru) {
+            chain.push(format!(".timeout(std::time::Duration::from_millis({}))", ms));
+        }
+
+        chain.push(".send()".to_string());
+        chain.push(".await?;".to_string());
+        body.push(method_chain(&chain));
+        body.push(String::new());
+        body.push("println!(\"{}\", response.status());".to_string());
+        body.push("println!(\"{}\", response.text().await?);".to_string());
+        body.push("Ok(())".to_string());
+
+        let dependency = if features.is_empty() {
+            "reqwest = \"0.12\"".to_string()
+        } else {
+            let features: Vec<String> = features.iter().map(|feature| format!("\"{}\"", feature)).collect();
+            format!("reqwest = {{ version = \"0.12\", features = [{}] }}", features.join(", "))
+        };
+        let serde_json = if features.contains(&"json") { ", serde_json = \"1\"" } else { "" };
+
+        let mut lines = vec![
+            format!("// Cargo.toml: {}{}", dependency, serde_json),
+            "async fn send_request() -> Result<(), Box<dyn std::error::Error>> {".to_string(),
+        ];
+        for statement in body {
+            if statement.is_empty() {
+                lines.push(String::new());
+            } else {
+                lines.push(format!("{}{}", INDENT, statement.replace('\n', &format!("\n{}", INDENT))));
+            }
+        }
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+
+    fn warnings(&self, _bru: &BruFile, options: &RenderOptions) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if options.tls.passphrase.is_some() && !options.tls.is_pfx() {
+            warnings.push("reqwest has no option for the client key passphrase; it is left out".to_string());
+        }
+        if options.cookie_jar.is_some() {
+            warnings.push("reqwest has no cookie jar file; --cookie-jar is left out".to_string());
+        }
+        if !options.extra_args.is_empty() {
+            warnings.push(format!("extra arguments are left out: {}", options.extra_args.join(" ")));
+        }
+        warnings
+    }
+}
+
+/// The statement creating `client`.
+fn client(options: &RenderOptions) -> Vec<String> {
+    let mut chain = vec!["let client = reqwest::Client::builder()".to_string()];
+
+    if let Some(url) = proxy_url(&options.proxy) {
+        let no_proxy = match &options.proxy.no_proxy {
+            Some(hosts) => format!(".no_proxy(reqwest::NoProxy::from_string({}))", rust_str(hosts)),
+            None => String::new(),
+        };
+        chain.push(format!(".proxy(reqwest::Proxy::all({})?{})", rust_str(&url), no_proxy));
+    } else if options.proxy.no_proxy.as_deref() == Some("*") {
+        chain.push(".no_proxy()".to_string());
+    }
+
+    let tls = &options.tls;
+    if let Some(cert) = &tls.cert {
+        let identity = match &tls.key {
+            _ if tls.is_pfx() => format!(
+                "reqwest::Identity::from_pkcs12_der(&std::fs::read({})?, {})?",
+                rust_str(cert),
+                rust_str(tls.passphrase.as_deref().unwrap_or_default())
+            ),
+            Some(key) => format!(
+                "reqwest::Identity::from_pem(&[std::fs::read({})?, std::fs::read({})?].concat())?",
+                rust_str(cert),
+                rust_str(key)
+            ),
+            None => format!("reqwest::Identity::from_pem(&std::fs::read({})?)?", rust_str(cert)),
+        };
+        // A PEM identity only works with the rustls backend.
+        if !tls.is_pfx() {
+            chain.push(".use_rustls_tls()".to_string());
+        }
+        chain.push(format!(".identity({})", identity));
+    }
+    if let Some(ca_cert) = &tls.ca_cert {
+        chain.push(format!(
+            ".add_root_certificate(reqwest::Certificate::from_pem(&std::fs::read({})?)?)",
+            rust_str(ca_cert)
+        ));
+    }
+    if tls.insecure {
+        chain.push(".danger_accept_invalid_certs(true)".to_string());
+    }
+
+    if chain.len() == 1 {
+        return vec!["let client = reqwest::Client::new();".to_string(), String::new()];
+    }
+    chain.push(".build()?;".to_string());
+    vec![method_chain(&chain), String::new()]
+}
+
+/// The statement building `form` for a multipart body.
+fn multipart_form(body: &Body) -> Option<Vec<String>> {
+    if body.body_type != "multipart-form" {
+        return None;
+    }
+
+    let mut chain = vec!["let form = reqwest::multipart::Form::new()".to_string()];
+    for (name, value) in &body.fields {
+        chain.push(match file_path(value) {
+            Some(path) => {
+                let file_name = path.rsplit(['/', '\\']).next().unwrap_or(path);
+                format!(
+                    ".part({}, reqwest::multipart::Part::bytes(std::fs::read({})?).file_name({}))",
+                    rust_str(name),
+                    rust_str(path),
+                    rust_str(file_name)
+                )
+            }
+            None => format!(".text({}, {})", rust_str(name), rust_str(value)),
+        });
+    }
+
+    let mut statement = method_chain(&chain);
+    statement.push(';');
+    Some(vec![statement, String::new()])
+}
+
+/// Puts each call of a builder chain on its own line.
+fn method_chain(calls: &[String]) -> String {
+    let separator = format!("\n{}", INDENT);
+    calls.iter().map(|call| call.replace('\n', &separator)).collect::<Vec<_>>().join(&separator)
+}
+
+/// Whether the body is a plain string, which reqwest sends without a Content-Type.
+fn body_is_raw(body: &Body) -> bool {
+    match body.body_type.as_str() {
+        "multipart-form" | "form-urlencoded" => false,
+        _ if body.content.is_empty() => false,
+        "json" => json_literal(&body.content).is_none(),
+        _ => true,
+    }
+}
+
+/// The builder call that attaches the body.
+fn body_call(body: &Body) -> Option<String> {
+    match body.body_type.as_str() {
+        "multipart-form" => Some(".multipart(form)".to_string()),
+        "form-urlencoded" if body.fields.is_empty() => None,
+        "form-urlencoded" => Some(format!(".form(&{})", rust_pairs(&body.fields))),
+        _ if body.content.is_empty() => None,
+        "json" => Some(match json_literal(&body.content) {
+            Some(json) => format!(".json(&serde_json::json!({}))", json),
+            None => format!(".body({})", rust_str(&body.content)),
+        }),
+        _ => Some(format!(".body({})", rust_str(&body.content))),
+    }
+}
+
+/// The JSON body as `serde_json::json!` input, with each string written out
+/// again as a Rust literal. `None` when the macro cannot take the body: a
+/// number has no Rust literal, or the body is too big for its recursion limit.
+fn json_literal(content: &str) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(content).ok()?;
+    if macro_depth(&value) > MAX_MACRO_DEPTH {
+        return None;
+    }
+
+    let json = pretty_print_indented(content, INDENT)?;
+    let representable = Cell::new(true);
+    let literal = rewrite_tokens(&json, rust_str, |literal| {
+        number_literal(literal).unwrap_or_else(|| {
+            representable.set(false);
+            literal.to_string()
+        })
+    })?;
+    representable.get().then_some(literal)
+}
+
+/// rustc's default macro recursion limit is 128; leave some room.
+const MAX_MACRO_DEPTH: usize = 100;
+
+/// An upper bound on how deep `json!` recurses for `value`: it takes about
+/// one level per array element, three per object member and four per nesting.
+fn macro_depth(value: &serde_json::Value) -> usize {
+    let (items, per_item): (Vec<&serde_json::Value>, usize) = match value {
+        serde_json::Value::Array(items) => (items.iter().collect(), 1),
+        serde_json::Value::Object(members) => (members.values().collect(), 3),
+        _ => return 0,
+    };
+    let deepest = items.iter().map(|item| macro_depth(item)).max().unwrap_or(0);
+    4 + per_item * items.len() + deepest
+}
+
+/// A JSON literal as Rust reads it inside `json!`. Integers default to `i32`
+/// there, so wider ones get a suffix; `None` for numbers no Rust type holds.
+fn number_literal(literal: &str) -> Option<String> {
+    if matches!(literal, "true" | "false" | "null") || literal.parse::<i32>().is_ok() {
+        Some(literal.to_string())
+    } else if literal.parse::<i64>().is_ok() {
+        Some(format!("{}i64", literal))
+    } else if literal.parse::<u64>().is_ok() {
+        Some(format!("{}u64", literal))
+    } else if literal.contains(['.', 'e', 'E']) && literal.parse::<f64>().is_ok_and(f64::is_finite) {
+        Some(literal.to_string())
+    } else {
+        None
+    }
+}
+
+/// A slice of string pairs, for `.query()` and `.form()`.
+fn rust_pairs(pairs: &[(String, String)]) -> String {
+    let items: Vec<String> = pairs
+        .iter()
+        .map(|(name, value)| format!("{}({}, {}),", INDENT, rust_str(name), rust_str(value)))
+        .collect();
+    format!("[\n{}\n]", items.join("\n"))
+}
+
+/// A double-quoted Rust string literal.
+fn rust_str(value: &str) -> String {
+    let mut result = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '\\' => result.push_str("\\\\"),
+            '"' => result.push_str("\\\""),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            '\0' => result.push_str("\\0"),
+            c if c.is_control() => result.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::tests::{make_bru, render, with_body};
+    use crate::proxy::ProxySettings;
+    use crate::tls::TlsSettings;
+
+    #[test]
+    fn get_with_query_and_headers() {
+        let mut bru = make_bru(Method::Get, "https://h/users?page=2");
+        bru.headers.push(("Accept".to_string(), "application/json".to_string()));
+        assert_eq!(
+            render(&Reqwest, &bru),
+            "// Cargo.toml: reqwest = \"0.12\"\n\
+             async fn send_request() -> Result<(), Box<dyn std::error::Error>> {\n    \
+             let client = reqwest::Client::new();\n\n    \
+             let response = client\n        \
+             .get(\"https://h/users\")\n        \
+             .query(&[\n            (\"page\", \"2\"),\n        ])\n        \
+             .header(\"Accept\", \"application/json\")\n        \
+             .send()\n        \
+             .await?;\n\n    \
+             println!(\"{}\", response.status());\n    \
+             println!(\"{}\", response.text().await?);\n    \
+             Ok(())\n}"
+        );
+    }
+
+    #[test]
+    fn json_body_uses_json_macro() {
+        let mut bru = with_body(make_bru(Method::Post, "https://h"), "json", r#"{"name": "Joé", "ids": [1, null]}"#);
+        bru.auth = Some(Auth::Bearer { token: "t0k".to_string() });
+        bru.settings.insert("timeout".to_string(), "2500".to_string());
+        let rust = render(&Reqwest, &bru);
+        assert!(rust.starts_with("// Cargo.toml: reqwest = { version = \"0.12\", features = [\"json\"] }, serde_json = \"1\"\n"));
+        assert!(rust.contains(
+            "        .json(&serde_json::json!({\n            \"name\": \"Joé\",\n            \"ids\": [\n                1,\n                null\n            ]\n        }))\n\
+             \x20       .bearer_auth(\"t0k\")\n\
+             \x20       .timeout(std::time::Duration::from_millis(2500))\n"
+        ));
+        assert!(!rust.contains("Content-Type"));
+
+        let invalid = with_body(make_bru(Method::Post, "https://h"), "json", "{{BODY}}");
+        let rust = render(&Reqwest, &invalid);
+        assert!(rust.contains(".header(\"Content-Type\", \"application/json\")\n        .body(\"{{BODY}}\")"));
+    }
+
+    #[test]
+    fn json_the_macro_cannot_take_becomes_a_string() {
+        let wide = with_body(make_bru(Method::Post, "https://h"), "json", "[10000000000, 18446744073709551615, 1e3]");
+        assert!(render(&Reqwest, &wide).contains("10000000000i64,\n            18446744073709551615u64,\n            1e3\n"));
+
+        let huge = with_body(make_bru(Method::Post, "https://h"), "json", r#"{"n": 123456789012345678901234567890}"#);
+        let rust = render(&Reqwest, &huge);
+        assert!(rust.contains(".header(\"Content-Type\", \"application/json\")\n        .body(\"{\\\"n\\\": 1234"));
+        assert!(!rust.contains("serde_json"));
+
+        let long = format!("[{}]", vec!["1"; 200].join(","));
+        assert!(!render(&Reqwest, &with_body(make_bru(Method::Post, "https://h"), "json", &long)).contains("json!"));
+    }
+
+    #[test]
+    fn forms_and_multipart() {
+        let mut form = with_body(make_bru(Method::Post, "https://h"), "form-urlencoded", "");
+        form.body.as_mut().unwrap().fields = vec![("q".to_string(), "a b".to_string())];
+        assert!(render(&Reqwest, &form).contains("        .form(&[\n            (\"q\", \"a b\"),\n        ])\n"));
+        form.body.as_mut().unwrap().fields.clear();
+        assert!(!render(&Reqwest, &form).contains(".form("));
+
+        let mut multipart = with_body(make_bru(Method::Options, "https://h"), "multipart-form", "");
+        multipart.body.as_mut().unwrap().fields =
+            vec![("title".to_string(), "x".to_string()), ("doc".to_string(), "@file(docs/a.pdf)".to_string())];
+        let rust = render(&Reqwest, &multipart);
+        assert!(rust.contains(
+            "    let form = reqwest::multipart::Form::new()\n        \
+             .text(\"title\", \"x\")\n        \
+             .part(\"doc\", reqwest::multipart::Part::bytes(std::fs::read(\"docs/a.pdf\")?).file_name(\"a.pdf\"));\n"
+        ));
+        assert!(rust.contains(".request(reqwest::Method::OPTIONS, \"https://h\")\n        .multipart(form)\n"));
+        assert!(rust.contains("features = [\"multipart\"]"));
+    }
+
+    #[test]
+    fn basic_auth_raw_body_and_client_settings() {
+        let mut bru = with_body(make_bru(Method::Put, "https://h"), "xml", "<a>\"</a>");
+        bru.auth = Some(Auth::Basic { username: "u".to_string(), password: "p".to_string() });
+        let options = RenderOptions {
+            proxy: ProxySettings {
+                url: Some("http://proxy:3128".to_string()),
+                user: None,
+                no_proxy: Some("localhost".to_string()),
+            },
+            tls: TlsSettings { ca_cert: Some("ca.pem".to_string()), insecure: true, ..Default::default() },
+            ..Default::default()
+        };
+        let rust = Reqwest.render(&bru, &options);
+        assert!(rust.contains(
+            "    let client = reqwest::Client::builder()\n        \
+             .proxy(reqwest::Proxy::all(\"http://proxy:3128\")?.no_proxy(reqwest::NoProxy::from_string(\"localhost\")))\n        \
+             .add_root_certificate(reqwest::Certificate::from_pem(&std::fs::read(\"ca.pem\")?)?)\n        \
+             .danger_accept_invalid_certs(true)\n        \
+             .build()?;\n"
+        ));
+        assert!(rust.contains(
+            ".header(\"Content-Type\", \"application/xml\")\n        .body(\"<a>\\\"</a>\")\n        .basic_auth(\"u\", Some(\"p\"))\n"
+        ));
+    }
+
+    #[test]
+    fn client_certificates_pick_a_tls_feature() {
+        let bru = make_bru(Method::Get, "https://h");
+        let pem = RenderOptions {
+            tls: TlsSettings { cert: Some("c.pem".to_string()), key: Some("c.key".to_string()), ..Default::default() },
+            ..Default::default()
+        };
+        let rust = Reqwest.render(&bru, &pem);
+        assert!(rust.starts_with("// Cargo.toml: reqwest = { version = \"0.12\", features = [\"rustls-tls\"] }\n"));
+        assert!(rust.contains(".use_rustls_tls()\n        .identity(reqwest::Identity::from_pem("));
+
+        let pfx = RenderOptions {
+            tls: TlsSettings {
+                cert: Some("c.pfx".to_string()),
+                cert_type: Some("P12".to_string()),
+                passphrase: Some("pw".to_string()),
+                ..Default::default()
+            },
+            cookie_jar: Some("jar.txt".to_string()),
+            ..Default::default()
+        };
+        let rust = Reqwest.render(&bru, &pfx);
+        assert!(rust.contains("features = [\"native-tls\"]"));
+        assert!(rust.contains(".identity(reqwest::Identity::from_pkcs12_der(&std::fs::read(\"c.pfx\")?, \"pw\")?)"));
+        assert_eq!(Reqwest.warnings(&bru, &pfx), vec!["reqwest has no cookie jar file; --cookie-jar is left out"]);
+    }
+}
@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::dotenv::parse_dotenv;
+use crate::parser::parse_pre_request_vars;
+
+/// Where a layer of variables comes from. Layers are consulted in the order
+/// they were pushed, so callers push them from highest to lowest precedence:
+/// CLI overrides, request, folders (nearest first), collection, environment,
+/// global environment and finally `process.env`. Secret store values sit just
+/// above the environment that lists them.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    Cli,
+    VarFile(PathBuf),
+    Request,
+    Folder(PathBuf),
+    Collection,
+    Environment(String),
+    Global(String),
+    /// Values of `vars:secret` keys, described by their store.
+    SecretStore(String),
+    ProcessEnv,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Cli => write!(f, "--var"),
+            Source::VarFile(path) => write!(f, "--var-file {}", path.display()),
+            Source::Request => write!(f, "request vars"),
+            Source::Folder(path) => write!(f, "folder {}", path.display()),
+            Source::Collection => write!(f, "collection vars"),
+            Source::Environment(name) => write!(f, "environment {}", name),
+            Source::Global(name) => write!(f, "global environment {}", name),
+            Source::SecretStore(store) => write!(f, "secret store ({})", store),
+            Source::ProcessEnv => write!(f, "process.env"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Layer {
+    pub source: Source,
+    pub vars: HashMap<String, String>,
+    /// The file the variables were read from, when there is one.
+    pub file: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Resolver {
+    layers: Vec<Layer>,
+}
+
+impl Resolver {
+    /// Adds a layer below every layer pushed so far.
+    pub fn push(&mut self, source: Source, vars: HashMap<String, String>) {
+        self.layers.push(Layer { source, vars, file: None });
+    }
+
+    /// Like `push`, remembering which file the variables came from.
+    pub fn push_file(&mut self, source: Source, vars: HashMap<String, String>, file: PathBuf) {
+        self.layers.push(Layer { source, vars, file: Some(file) });
+    }
+
+    /// Looks a `{{name}}` up through every layer except `process.env`, which
+    /// is only reachable through `{{process.env.NAME}}`.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.layers
+            .iter()
+            .filter(|layer| layer.source != Source::ProcessEnv)
+            .find_map(|layer| layer.vars.get(name))
+            .map(String::as_str)
+    }
+
+    /// Every layer that defines `name`, the winning one first. As with `get`,
+    /// `process.env` is not included.
+    pub fn definitions(&self, name: &str) -> Vec<&Layer> {
+        self.layers
+            .iter()
+            .filter(|layer| layer.source != Source::ProcessEnv && layer.vars.contains_key(name))
+            .collect()
+    }
+
+    pub fn process_env(&self, name: &str) -> Option<&str> {
+        self.layers
+            .iter()
+            .filter(|layer| layer.source == Source::ProcessEnv)
+            .find_map(|layer| layer.vars.get(name))
+            .map(String::as_str)
+    }
+}
+
+pub fn parse_var_override(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => Ok((key.trim().to_string(), value.to_string())),
+        _ => Err(format!("Invalid variable override '{}': expected KEY=VALUE", s)),
+    }
+}
+
+pub fn load_var_file(path: &Path) -> Result<HashMap<String, String>, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Cannot read var file {:?}: {}", path, e))?;
+    Ok(parse_dotenv(&content))
+}
+
+type FolderVars = (PathBuf, HashMap<String, String>);
+
+/// Reads `vars:pre-request` from every `folder.bru` between the request and
+/// the collection root, nearest folder first. Folders `is_ignored` rejects
+/// are skipped.
+pub fn load_folder_vars<F>(
+    bru_file_path: &Path,
+    collection_root: &Path,
+    is_ignored: F,
+) -> Result<Vec<FolderVars>, String>
+where
+    F: Fn(&Path) -> bool,
+{
+    let mut layers = Vec::new();
+    let mut current = bru_file_path.parent();
+
+    while let Some(dir) = current {
+        if dir == collection_root || !dir.starts_with(collection_root) {
+            break;
+        }
+        let folder_file = dir.join("folder.bru");
+        if folder_file.exists() && !is_ignored(dir) {
+            let content = fs::read_to_string(&folder_file)
+                .map_err(|e| format!("Cannot read {:?}: {}", folder_file, e))?;
+            layers.push((dir.to_path_buf(), parse_pre_request_vars(&content)));
+        }
+        current = dir.parent();
+    }
+
+    Ok(layers)
+}
+
+pub fn load_collection_vars(collection_root: &Path) -> Result<HashMap<String, String>, String> {
+    let collection_file = collection_root.join("collection.bru");
+    if !collection_file.exists() {
+        return Ok(HashMap::new());
+    }
+    let content = fs::read_to_string(&collection_file)
+        .map_err(|e| format!("Cannot read collection.bru: {}", e))?;
+    Ok(parse_pre_request_vars(&content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn earlier_layers_take_precedence() {
+        let mut resolver = Resolver::default();
+        resolver.push(Source::Cli, vars(&[("ID", "cli")]));
+        resolver.push(Source::Request, vars(&[("ID", "request"), ("PAGE", "1")]));
+        resolver.push(Source::Environment("Local".into()), vars(&[("ID", "env"), ("HOST", "h")]));
+
+        assert_eq!(resolver.get("ID"), Some("cli"));
+        assert_eq!(resolver.get("PAGE"), Some("1"));
+        assert_eq!(resolver.get("HOST"), Some("h"));
+        assert_eq!(resolver.get("NOPE"), None);
+    }
+
+    #[test]
+    fn definitions_list_shadowed_layers() {
+        let mut resolver = Resolver::default();
+        resolver.push(Source::Cli, vars(&[("ID", "cli")]));
+        resolver.push_file(Source::Environment("Local".into()), vars(&[("ID", "env")]), PathBuf::from("environments/Local.bru"));
+        resolver.push(Source::ProcessEnv, vars(&[("ID", "process")]));
+
+        let definitions = resolver.definitions("ID");
+        assert_eq!(definitions.len(), 2);
+        assert_eq!(definitions[0].source, Source::Cli);
+        assert_eq!(definitions[1].file.as_deref(), Some(Path::new("environments/Local.bru")));
+        assert!(resolver.definitions("NOPE").is_empty());
+    }
+
+    #[test]
+    fn process_env_is_only_reachable_by_prefix() {
+        let mut resolver = Resolver::default();
+        resolver.push(Source::ProcessEnv, vars(&[("HOME", "/home/me")]));
+
+        assert_eq!(resolver.get("HOME"), None);
+        assert_eq!(resolver.process_env("HOME"), Some("/home/me"));
+    }
+
+    #[test]
+    fn parse_overrides() {
+        assert_eq!(parse_var_override("ID=42").unwrap(), ("ID".to_string(), "42".to_string()));
+        assert_eq!(parse_var_override("Q=a=b").unwrap(), ("Q".to_string(), "a=b".to_string()));
+        assert!(parse_var_override("novalue").is_err());
+    }
+
+    #[test]
+    fn folder_and_collection_vars() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        let nested = root.join("users/admin");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(root.join("collection.bru"), "vars:pre-request {\n  SCOPE: collection\n}\n").unwrap();
+        fs::write(root.join("users/folder.bru"), "meta {\n  name: users\n}\n\nvars:pre-request {\n  SCOPE: users\n}\n").unwrap();
+        fs::write(nested.join("folder.bru"), "vars:pre-request {\n  SCOPE: admin\n}\n").unwrap();
+
+        let folders = load_folder_vars(&nested.join("get.bru"), root, |_| false).unwrap();
+        assert_eq!(folders.len(), 2);
+        assert_eq!(folders[0].0, nested);
+        assert_eq!(folders[0].1.get("SCOPE").unwrap(), "admin");
+        assert_eq!(folders[1].1.get("SCOPE").unwrap(), "users");
+
+        let collection = load_collection_vars(root).unwrap();
+        assert_eq!(collection.get("SCOPE").unwrap(), "collection");
+
+        let skipping_admin = load_folder_vars(&nested.join("get.bru"), root, |dir| dir.ends_with("admin")).unwrap();
+        assert_eq!(skipping_admin.len(), 1);
+        assert_eq!(skipping_admin[0].1.get("SCOPE").unwrap(), "users");
+    }
+}
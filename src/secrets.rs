@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::dotenv::parse_dotenv;
+use crate::parser::ast::Environment;
+
+pub const DEFAULT_SECRETS_FILE: &str = ".env.secrets";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SecretSource {
+    /// A dotenv-style file; relative paths start at the collection root.
+    File(PathBuf),
+    Env(String),
+    Command(String),
+}
+
+impl std::str::FromStr for SecretSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some(("file", path)) if !path.is_empty() => Ok(SecretSource::File(PathBuf::from(path))),
+            Some(("env", prefix)) => Ok(SecretSource::Env(prefix.to_string())),
+            Some(("cmd", command)) if !command.is_empty() => Ok(SecretSource::Command(command.to_string())),
+            _ => Err(format!(
+                "Invalid secret source '{}': expected file:<path>, env:<prefix> or cmd:<command>",
+                s
+            )),
+        }
+    }
+}
+
+impl SecretSource {
+    pub fn describe(&self) -> String {
+        match self {
+            SecretSource::File(path) => format!("file {}", path.display()),
+            SecretSource::Env(prefix) => format!("environment variables {}*", prefix),
+            SecretSource::Command(command) => format!("command `{}`", command),
+        }
+    }
+
+    fn fetch(&self, keys: &[String], collection_root: &Path) -> Result<HashMap<String, String>, String> {
+        match self {
+            SecretSource::File(path) => {
+                let path = collection_root.join(path);
+                let content = fs::read_to_string(&path)
+                    .map_err(|e| format!("Cannot read secrets file {:?}: {}", path, e))?;
+                Ok(parse_dotenv(&content))
+            }
+            SecretSource::Env(prefix) => Ok(keys
+                .iter()
+                .filter_map(|key| {
+                    std::env::var(format!("{}{}", prefix, key))
+                        .ok()
+                        .map(|value| (key.clone(), value))
+                })
+                .collect()),
+            SecretSource::Command(command) => {
+                let output = shell_command(command)
+                    .current_dir(collection_root)
+                    .output()
+                    .map_err(|e| format!("Cannot run secrets command: {}", e))?;
+                if !output.status.success() {
+                    return Err(format!(
+                        "Secrets command failed ({}): {}",
+                        output.status,
+                        String::from_utf8_lossy(&output.stderr).trim()
+                    ));
+                }
+                Ok(parse_dotenv(&String::from_utf8_lossy(&output.stdout)))
+            }
+        }
+    }
+}
+
+fn shell_command(command: &str) -> Command {
+    if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", command]);
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", command]);
+        cmd
+    }
+}
+
+/// Looks up the values of `vars:secret` keys in `source`, falling back to
+/// `.env.secrets` at the collection root when no source is given. Returns the
+/// values found and the secret keys that are still without a value.
+pub fn resolve_secrets(
+    env: &Environment,
+    source: Option<&SecretSource>,
+    collection_root: &Path,
+) -> Result<(HashMap<String, String>, Vec<String>), String> {
+    if env.secrets.is_empty() {
+        return Ok((HashMap::new(), Vec::new()));
+    }
+
+    let values = match source {
+        Some(source) => source.fetch(&env.secrets, collection_root)?,
+        None => {
+            let default = SecretSource::File(PathBuf::from(DEFAULT_SECRETS_FILE));
+            if collection_root.join(DEFAULT_SECRETS_FILE).exists() {
+                default.fetch(&env.secrets, collection_root)?
+            } else {
+                HashMap::new()
+            }
+        }
+    };
+
+    let values: HashMap<String, String> = values.into_iter().filter(|(key, _)| env.secrets.contains(key)).collect();
+    let missing = env
+        .secrets
+        .iter()
+        .filter(|key| !values.contains_key(*key) && env.vars.get(*key).is_none_or(|v| v.is_empty()))
+        .cloned()
+        .collect();
+
+    Ok((values, missing))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn env_with_secrets(keys: &[&str]) -> Environment {
+        Environment {
+            secrets: keys.iter().map(|k| k.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parse_secret_source() {
+        assert_eq!("file:.secrets".parse::<SecretSource>().unwrap(), SecretSource::File(".secrets".into()));
+        assert_eq!("env:BRUQ_".parse::<SecretSource>().unwrap(), SecretSource::Env("BRUQ_".into()));
+        assert_eq!("cmd:pass show api".parse::<SecretSource>().unwrap(), SecretSource::Command("pass show api".into()));
+        assert!("vault:x".parse::<SecretSource>().is_err());
+    }
+
+    #[test]
+    fn resolve_from_default_file() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join(DEFAULT_SECRETS_FILE), "API_KEY=abc\n").unwrap();
+
+        let env = env_with_secrets(&["API_KEY", "DB_PASSWORD"]);
+        let (values, missing) = resolve_secrets(&env, None, temp.path()).unwrap();
+
+        assert_eq!(values.get("API_KEY").unwrap(), "abc");
+        assert_eq!(missing, vec!["DB_PASSWORD"]);
+    }
+
+    #[test]
+    fn missing_default_file_reports_all_secrets() {
+        let temp = TempDir::new().unwrap();
+        let env = env_with_secrets(&["API_KEY"]);
+        let (_, missing) = resolve_secrets(&env, None, temp.path()).unwrap();
+        assert_eq!(missing, vec!["API_KEY"]);
+    }
+
+    #[test]
+    fn relative_file_is_resolved_against_collection_root() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().join("api");
+        fs::create_dir_all(temp.path().join("shared")).unwrap();
+        fs::create_dir_all(&root).unwrap();
+        fs::write(temp.path().join("shared/.env.secrets"), "API_KEY=shared\n").unwrap();
+
+        let env = env_with_secrets(&["API_KEY"]);
+        let source: SecretSource = "file:../shared/.env.secrets".parse().unwrap();
+        let (values, missing) = resolve_secrets(&env, Some(&source), &root).unwrap();
+
+        assert!(missing.is_empty());
+        assert_eq!(values.get("API_KEY").unwrap(), "shared");
+    }
+
+    #[test]
+    fn explicit_missing_file_is_an_error() {
+        let temp = TempDir::new().unwrap();
+        let env = env_with_secrets(&["API_KEY"]);
+        let source = SecretSource::File("nope.env".into());
+        assert!(resolve_secrets(&env, Some(&source), temp.path()).is_err());
+    }
+
+    #[test]
+    fn resolve_from_command() {
+        let temp = TempDir::new().unwrap();
+        let env = env_with_secrets(&["TOKEN"]);
+        let source = SecretSource::Command("echo TOKEN=from-cmd".into());
+        let (values, missing) = resolve_secrets(&env, Some(&source), temp.path()).unwrap();
+
+        assert!(missing.is_empty());
+        assert_eq!(values.get("TOKEN").unwrap(), "from-cmd");
+    }
+}
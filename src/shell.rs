@@ -0,0 +1,369 @@
+use clap::ValueEnum;
+
+/// The shell the generated command is meant to be pasted into or evaluated by.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Shell {
+    /// sh, bash, zsh and other POSIX shells
+    #[default]
+    Posix,
+    Fish,
+    /// PowerShell 7.3+ (earlier versions mangle embedded double quotes)
+    Powershell,
+    /// cmd.exe
+    Cmd,
+}
+
+/// Characters that never need quoting, per shell.
+const POSIX_SAFE: &str = "_-./:,=+@%";
+const FISH_SAFE: &str = "_-./:,=+@";
+const POWERSHELL_SAFE: &str = "_-./:=+";
+const CMD_SAFE: &str = "_-./:,=+@";
+
+/// Characters cmd.exe acts on even inside double quotes, escaped with `^`.
+const CMD_SPECIAL: &str = "^&|<>()%!\"";
+
+/// PowerShell treats the typographic single quotes as `'` too.
+const POWERSHELL_QUOTES: [char; 5] = ['\'', '\u{2018}', '\u{2019}', '\u{201A}', '\u{201B}'];
+
+impl Shell {
+    /// Picks the shell from a `$SHELL` value; without one, PowerShell on
+    /// Windows and POSIX elsewhere.
+    pub fn detect(shell_var: Option<&str>) -> Shell {
+        let Some(shell_var) = shell_var.filter(|s| !s.is_empty()) else {
+            return if cfg!(windows) { Shell::Powershell } else { Shell::Posix };
+        };
+
+        // Split by hand so Windows paths work on every platform.
+        let file_name = shell_var.rsplit(['/', '\\']).next().unwrap_or(shell_var).to_lowercase();
+        match file_name.strip_suffix(".exe").unwrap_or(&file_name) {
+            "fish" => Shell::Fish,
+            "pwsh" | "powershell" => Shell::Powershell,
+            "cmd" => Shell::Cmd,
+            _ => Shell::Posix,
+        }
+    }
+
+    pub fn from_env() -> Shell {
+        Shell::detect(std::env::var("SHELL").ok().as_deref())
+    }
+
+    /// In PowerShell `curl` is an alias for `Invoke-WebRequest`.
+    pub fn curl_program(self) -> &'static str {
+        match self {
+            Shell::Powershell => "curl.exe",
+            _ => "curl",
+        }
+    }
+
+    /// A warning for when joining `args` loses a line break: cmd.exe cannot
+    /// pass one inside an argument, so `quote` turns it into a space.
+    pub fn line_break_warning(self, args: &[String]) -> Option<String> {
+        let drops = self == Shell::Cmd && args.iter().any(|arg| arg.contains(['\r', '\n']));
+        drops.then(|| "cmd.exe cannot pass line breaks inside an argument; they are replaced with spaces".to_string())
+    }
+
+    /// Quotes `arg` so this shell reads it back as exactly one word. Plain
+    /// words are left alone.
+    pub fn quote(self, arg: &str) -> String {
+        let safe = match self {
+            Shell::Posix => POSIX_SAFE,
+            Shell::Fish => FISH_SAFE,
+            Shell::Powershell => POWERSHELL_SAFE,
+            Shell::Cmd => CMD_SAFE,
+        };
+        if !arg.is_empty() && arg.chars().all(|c| c.is_ascii_alphanumeric() || safe.contains(c)) {
+            return arg.to_string();
+        }
+
+        match self {
+            Shell::Posix => format!("'{}'", arg.replace('\'', r"'\''")),
+            Shell::Fish => format!("'{}'", arg.replace('\\', r"\\").replace('\'', r"\'")),
+            Shell::Powershell => {
+                let mut quoted = String::from("'");
+                for c in arg.chars() {
+                    if POWERSHELL_QUOTES.contains(&c) {
+                        quoted.push(c);
+                    }
+                    quoted.push(c);
+                }
+                quoted.push('\'');
+                quoted
+            }
+            Shell::Cmd => quote_cmd(arg),
+        }
+    }
+
+    /// Joins `args` into a single command line for this shell.
+    pub fn join(self, args: &[String]) -> String {
+        args.iter().map(|arg| self.quote(arg)).collect::<Vec<_>>().join(" ")
+    }
+
+    /// The character that continues a command on the next line.
+    pub fn continuation(self) -> char {
+        match self {
+            Shell::Posix | Shell::Fish => '\\',
+            Shell::Powershell => '`',
+            Shell::Cmd => '^',
+        }
+    }
+
+    /// Like `join`, but puts each group of arguments on its own indented
+    /// line, ending every line but the last with a continuation.
+    pub fn join_lines(self, lines: &[Vec<String>]) -> String {
+        let separator = format!(" {}\n  ", self.continuation());
+        lines.iter().map(|line| self.join(line)).collect::<Vec<_>>().join(&separator)
+    }
+}
+
+/// Double-quotes `arg` the way the Windows C runtime splits command lines,
+/// then escapes every character cmd.exe would interpret with `^`. cmd.exe
+/// cannot pass line breaks inside an argument, so they become spaces.
+fn quote_cmd(arg: &str) -> String {
+    let arg = arg.replace("\r\n", " ").replace(['\r', '\n'], " ");
+
+    let mut quoted = String::from("\"");
+    let mut backslashes = 0;
+    for c in arg.chars() {
+        match c {
+            '\\' => backslashes += 1,
+            '"' => {
+                quoted.push_str(&"\\".repeat(backslashes * 2 + 1));
+                backslashes = 0;
+            }
+            _ => {
+                quoted.push_str(&"\\".repeat(backslashes));
+                backslashes = 0;
+            }
+        }
+        if c != '\\' {
+            quoted.push(c);
+        }
+    }
+    quoted.push_str(&"\\".repeat(backslashes * 2));
+    quoted.push('"');
+
+    let mut escaped = String::new();
+    for c in quoted.chars() {
+        if CMD_SPECIAL.contains(c) {
+            escaped.push('^');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::process::Command;
+
+    /// Runs `line` through `sh` and returns the words it was split into.
+    pub(crate) fn sh_words(line: &str) -> Vec<String> {
+        let output = Command::new("sh")
+            .arg("-c")
+            .arg(format!("printf '%s\\0' {}", line))
+            .output()
+            .expect("sh is available");
+        assert!(output.status.success(), "sh failed on {}", line);
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        stdout.split_terminator('\0').map(String::from).collect()
+    }
+
+    /// fish: whitespace splits words; inside '...' only \' and \\ are escapes.
+    /// Unquoted text must not contain anything fish would expand.
+    fn fish_words(line: &str) -> Vec<String> {
+        let mut words = Vec::new();
+        let mut chars = line.chars().peekable();
+        while let Some(&c) = chars.peek() {
+            if c == ' ' {
+                chars.next();
+                continue;
+            }
+            let mut word = String::new();
+            while let Some(c) = chars.next_if(|&c| c != ' ') {
+                if c != '\'' {
+                    assert!(c.is_ascii_alphanumeric() || FISH_SAFE.contains(c), "unquoted {:?} in {}", c, line);
+                    word.push(c);
+                    continue;
+                }
+                loop {
+                    match chars.next().expect("closing quote") {
+                        '\'' => break,
+                        '\\' if matches!(chars.peek(), Some('\'') | Some('\\')) => word.push(chars.next().unwrap()),
+                        c => word.push(c),
+                    }
+                }
+            }
+            words.push(word);
+        }
+        words
+    }
+
+    /// PowerShell: inside '...' a doubled quote character stands for itself.
+    fn powershell_words(line: &str) -> Vec<String> {
+        let mut words = Vec::new();
+        let mut chars = line.chars().peekable();
+        while let Some(&c) = chars.peek() {
+            if c == ' ' {
+                chars.next();
+                continue;
+            }
+            let mut word = String::new();
+            while let Some(c) = chars.next_if(|&c| c != ' ') {
+                if !POWERSHELL_QUOTES.contains(&c) {
+                    assert!(c.is_ascii_alphanumeric() || POWERSHELL_SAFE.contains(c), "unquoted {:?} in {}", c, line);
+                    word.push(c);
+                    continue;
+                }
+                loop {
+                    let c = chars.next().expect("closing quote");
+                    if !POWERSHELL_QUOTES.contains(&c) {
+                        word.push(c);
+                    } else if chars.peek().is_some_and(|n| POWERSHELL_QUOTES.contains(n)) {
+                        word.push(chars.next().unwrap());
+                    } else {
+                        break;
+                    }
+                }
+            }
+            words.push(word);
+        }
+        words
+    }
+
+    /// cmd.exe strips `^` escapes (every special character must carry one),
+    /// then the C runtime splits the rest into argv.
+    fn cmd_words(line: &str) -> Vec<String> {
+        let mut unescaped = String::new();
+        let mut chars = line.chars();
+        while let Some(c) = chars.next() {
+            if c == '^' {
+                unescaped.push(chars.next().expect("escaped character"));
+            } else {
+                assert!(!CMD_SPECIAL.contains(c), "unescaped {:?} in {}", c, line);
+                unescaped.push(c);
+            }
+        }
+
+        let mut words = Vec::new();
+        let mut chars = unescaped.chars().peekable();
+        while let Some(&c) = chars.peek() {
+            if c == ' ' {
+                chars.next();
+                continue;
+            }
+            let mut word = String::new();
+            let mut in_quotes = false;
+            while let Some(&c) = chars.peek() {
+                if c == ' ' && !in_quotes {
+                    break;
+                }
+                chars.next();
+                match c {
+                    '\\' => {
+                        let mut count = 1;
+                        while chars.next_if_eq(&'\\').is_some() {
+                            count += 1;
+                        }
+                        if chars.peek() == Some(&'"') {
+                            word.push_str(&"\\".repeat(count / 2));
+                            if count % 2 == 1 {
+                                word.push(chars.next().unwrap());
+                            }
+                        } else {
+                            word.push_str(&"\\".repeat(count));
+                        }
+                    }
+                    '"' => in_quotes = !in_quotes,
+                    c => word.push(c),
+                }
+            }
+            words.push(word);
+        }
+        words
+    }
+
+    /// Random argument vectors built from characters that trip up shells.
+    fn random_args(seed: u64, rounds: usize, newlines: bool) -> Vec<Vec<String>> {
+        const ALPHABET: &[char] = &[
+            'a', 'Z', '0', ' ', '\t', '\n', '\'', '"', '\\', '$', '`', '!', '*', '?', '[', ']', '{', '}', '(', ')',
+            '<', '>', '|', '&', ';', '#', '~', '=', '%', '@', ':', '/', '-', '^', ',', 'é', '日', '\u{2019}',
+        ];
+        let mut state = seed;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as usize
+        };
+
+        (0..rounds)
+            .map(|_| {
+                (0..8)
+                    .map(|_| {
+                        (0..next() % 12)
+                            .map(|_| ALPHABET[next() % ALPHABET.len()])
+                            .filter(|&c| newlines || c != '\n')
+                            .collect()
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn quotes_only_when_needed() {
+        let posix = Shell::Posix;
+        assert_eq!(posix.quote("https://api.example.com/users"), "https://api.example.com/users");
+        assert_eq!(posix.quote("-X"), "-X");
+        assert_eq!(posix.quote(""), "''");
+        assert_eq!(posix.quote("a b"), "'a b'");
+        assert_eq!(posix.quote("http://h/?q=1&x=2"), "'http://h/?q=1&x=2'");
+        assert_eq!(posix.quote("~root"), "'~root'");
+        assert_eq!(posix.quote("O'Brien"), r"'O'\''Brien'");
+    }
+
+    #[test]
+    fn quoting_per_dialect() {
+        assert_eq!(Shell::Fish.quote(r"O'Brien\n"), r"'O\'Brien\\n'");
+        assert_eq!(Shell::Powershell.quote("O'Brien $HOME"), "'O''Brien $HOME'");
+        assert_eq!(Shell::Cmd.quote(r#"say "hi" 100% C:\dir\"#), r#"^"say \^"hi\^" 100^% C:\dir\\^""#);
+        assert_eq!(Shell::Cmd.quote("https://h/a"), "https://h/a");
+    }
+
+    #[test]
+    fn detects_shell_from_env_value() {
+        assert_eq!(Shell::detect(Some("/usr/bin/fish")), Shell::Fish);
+        assert_eq!(Shell::detect(Some("/usr/local/bin/pwsh")), Shell::Powershell);
+        assert_eq!(Shell::detect(Some("C:\\Windows\\System32\\cmd.exe")), Shell::Cmd);
+        assert_eq!(Shell::detect(Some("/bin/zsh")), Shell::Posix);
+        assert_eq!(Shell::Powershell.curl_program(), "curl.exe");
+    }
+
+    #[test]
+    fn continuation_lines_per_dialect() {
+        let lines = vec![vec!["curl".to_string()], vec!["-H".to_string(), "A: b".to_string()]];
+        assert_eq!(Shell::Posix.join_lines(&lines), "curl \\\n  -H 'A: b'");
+        assert_eq!(Shell::Fish.join_lines(&lines), "curl \\\n  -H 'A: b'");
+        assert_eq!(Shell::Powershell.join_lines(&lines), "curl `\n  -H 'A: b'");
+        assert_eq!(Shell::Cmd.join_lines(&lines), "curl ^\n  -H ^\"A: b^\"");
+    }
+
+    #[test]
+    fn sh_reads_back_the_original_words() {
+        for args in random_args(0x2545_f491_4f6c_dd1d, 20, true) {
+            assert_eq!(sh_words(&Shell::Posix.join(&args)), args);
+        }
+    }
+
+    #[test]
+    fn other_shells_read_back_the_original_words() {
+        for args in random_args(0x9e37_79b9_7f4a_7c15, 200, true) {
+            assert_eq!(fish_words(&Shell::Fish.join(&args)), args);
+            assert_eq!(powershell_words(&Shell::Powershell.join(&args)), args);
+        }
+        for args in random_args(0xd1b5_4a32_d192_ed03, 200, false) {
+            assert_eq!(cmd_words(&Shell::Cmd.join(&args)), args);
+        }
+    }
+}
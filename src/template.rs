@@ -0,0 +1,181 @@
+pub const MAX_DEPTH: usize = 16;
+
+/// What a placeholder name resolves to. `Template` values may reference other
+/// variables and are expanded in turn; `Literal` values are inserted verbatim.
+pub enum Resolution {
+    Template(String),
+    Literal(String),
+}
+
+/// Expands `{{name}}` placeholders in a single left-to-right pass. Unknown
+/// names are left untouched, and text produced by a substitution is never
+/// rescanned except when it is itself a variable's template.
+///
+/// `escape` sees each top-level value together with the text rendered before
+/// it, so callers can escape for the context the placeholder sits in.
+pub fn expand<F, E>(text: &str, resolve: &mut F, escape: &mut E) -> Result<String, String>
+where
+    F: FnMut(&str) -> Option<Resolution>,
+    E: FnMut(&str, &str, String) -> String,
+{
+    expand_inner(text, resolve, escape, &mut Vec::new())
+}
+
+fn expand_inner<F, E>(text: &str, resolve: &mut F, escape: &mut E, stack: &mut Vec<String>) -> Result<String, String>
+where
+    F: FnMut(&str) -> Option<Resolution>,
+    E: FnMut(&str, &str, String) -> String,
+{
+    let mut result = String::new();
+    let mut rest = text;
+
+    while let Some((start, end, name)) = next_placeholder(rest) {
+        result.push_str(&rest[..start]);
+
+        let value = match resolve(name) {
+            Some(Resolution::Literal(value)) => Some(value),
+            Some(Resolution::Template(value)) => {
+                if let Some(pos) = stack.iter().position(|n| n == name) {
+                    let mut cycle: Vec<&str> = stack[pos..].iter().map(String::as_str).collect();
+                    cycle.push(name);
+                    return Err(format!("Variable cycle detected: {}", cycle.join(" -> ")));
+                }
+                if stack.len() >= MAX_DEPTH {
+                    return Err(format!(
+                        "Variable nesting exceeds {} levels while expanding {}",
+                        MAX_DEPTH, stack[0]
+                    ));
+                }
+                stack.push(name.to_string());
+                let expanded = expand_inner(&value, resolve, &mut unescaped, stack)?;
+                stack.pop();
+                Some(expanded)
+            }
+            None => None,
+        };
+
+        match value {
+            Some(value) => {
+                let escaped = escape(&result, name, value);
+                result.push_str(&escaped);
+            }
+            None => result.push_str(&rest[start..end]),
+        }
+
+        rest = &rest[end..];
+    }
+
+    result.push_str(rest);
+    Ok(result)
+}
+
+fn unescaped(_preceding: &str, _name: &str, value: String) -> String {
+    value
+}
+
+/// Finds the next `{{ name }}` in `text`, returning its byte range and the
+/// trimmed name.
+pub fn next_placeholder(text: &str) -> Option<(usize, usize, &str)> {
+    let mut offset = 0;
+
+    loop {
+        let start = offset + text[offset..].find("{{")?;
+        let close = start + 2 + text[start + 2..].find("}}")?;
+
+        // "{{ {{NAME}}" - the innermost opening brace pair wins.
+        let inner_open = text[start + 2..close].rfind("{{").map(|p| start + 2 + p);
+        let start = inner_open.unwrap_or(start);
+
+        let name = text[start + 2..close].trim();
+        if !name.is_empty() && !name.contains('{') && !name.contains('}') {
+            return Some((start, close + 2, name));
+        }
+        offset = close + 2;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn expand_vars(text: &str, vars: &[(&str, &str)]) -> Result<String, String> {
+        let vars: HashMap<&str, &str> = vars.iter().copied().collect();
+        expand(
+            text,
+            &mut |name| vars.get(name).map(|v| Resolution::Template(v.to_string())),
+            &mut |_, _, value| value,
+        )
+    }
+
+    #[test]
+    fn expands_nested_variables() {
+        let vars = [("BASE_URL", "{{HOST}}:{{PORT}}"), ("HOST", "http://localhost"), ("PORT", "3000")];
+        assert_eq!(expand_vars("{{BASE_URL}}/users", &vars).unwrap(), "http://localhost:3000/users");
+    }
+
+    #[test]
+    fn tolerates_spaces_inside_braces() {
+        assert_eq!(expand_vars("Bearer {{ TOKEN }}", &[("TOKEN", "abc")]).unwrap(), "Bearer abc");
+    }
+
+    #[test]
+    fn leaves_unknown_placeholders_untouched() {
+        assert_eq!(expand_vars("{{ MISSING }}/{{A}}", &[("A", "a")]).unwrap(), "{{ MISSING }}/a");
+    }
+
+    #[test]
+    fn literal_values_are_not_rescanned() {
+        let result = expand(
+            "{{A}}",
+            &mut |name| match name {
+                "A" => Some(Resolution::Literal("{{B}}".to_string())),
+                _ => Some(Resolution::Literal("b".to_string())),
+            },
+            &mut |_, _, value| value,
+        );
+        assert_eq!(result.unwrap(), "{{B}}");
+    }
+
+    #[test]
+    fn escape_sees_preceding_text_for_top_level_values_only() {
+        let mut seen = Vec::new();
+        let result = expand(
+            "a={{OUTER}}",
+            &mut |name| match name {
+                "OUTER" => Some(Resolution::Template("<{{INNER}}>".to_string())),
+                _ => Some(Resolution::Literal("i".to_string())),
+            },
+            &mut |preceding, name, value| {
+                seen.push((preceding.to_string(), name.to_string()));
+                value.to_uppercase()
+            },
+        );
+        assert_eq!(result.unwrap(), "a=<I>");
+        assert_eq!(seen, vec![("a=".to_string(), "OUTER".to_string())]);
+    }
+
+    #[test]
+    fn detects_cycles() {
+        let err = expand_vars("{{A}}", &[("A", "x{{B}}"), ("B", "{{A}}")]).unwrap_err();
+        assert_eq!(err, "Variable cycle detected: A -> B -> A");
+
+        let err = expand_vars("{{SELF}}", &[("SELF", "{{ SELF }}")]).unwrap_err();
+        assert!(err.contains("SELF -> SELF"));
+    }
+
+    #[test]
+    fn limits_depth() {
+        let names: Vec<String> = (0..=MAX_DEPTH + 1).map(|i| format!("V{}", i)).collect();
+        let values: Vec<String> = (0..=MAX_DEPTH + 1).map(|i| format!("{{{{V{}}}}}", i + 1)).collect();
+        let vars: Vec<(&str, &str)> = names.iter().map(String::as_str).zip(values.iter().map(String::as_str)).collect();
+
+        let err = expand_vars("{{V0}}", &vars).unwrap_err();
+        assert!(err.contains("exceeds"));
+    }
+
+    #[test]
+    fn innermost_braces_win() {
+        assert_eq!(expand_vars("{{ {{A}}", &[("A", "a")]).unwrap(), "{{ a");
+    }
+}
@@ -0,0 +1,149 @@
+use std::path::Path;
+
+use crate::collection::{ClientCertificate, ClientCertificates};
+use crate::glob::glob_match;
+use crate::project::TlsDefaults;
+use crate::redact::MASK;
+
+/// The TLS options to hand to curl.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TlsSettings {
+    pub cert: Option<String>,
+    /// `P12` for PFX bundles; curl assumes PEM otherwise.
+    pub cert_type: Option<String>,
+    pub key: Option<String>,
+    pub passphrase: Option<String>,
+    pub ca_cert: Option<String>,
+    pub insecure: bool,
+}
+
+impl TlsSettings {
+    pub fn is_pfx(&self) -> bool {
+        self.cert_type.as_deref() == Some("P12")
+    }
+
+    pub fn redact(&mut self) {
+        if self.passphrase.is_some() {
+            self.passphrase = Some(MASK.to_string());
+        }
+    }
+}
+
+/// Picks the first client certificate whose domain matches the request host
+/// and adds the project-wide CA bundle and verification setting. Relative
+/// paths are resolved against the collection root.
+pub fn tls_settings(
+    certificates: Option<&ClientCertificates>,
+    defaults: &TlsDefaults,
+    request_url: &str,
+    collection_root: &Path,
+) -> TlsSettings {
+    let resolve = |path: &str| collection_root.join(path).display().to_string();
+
+    let mut settings = TlsSettings {
+        ca_cert: defaults.ca_cert.as_deref().map(resolve),
+        insecure: !defaults.ssl_verification,
+        ..Default::default()
+    };
+
+    let certificate = certificates
+        .filter(|certificates| certificates.enabled)
+        .and_then(|certificates| certificates.certs.iter().find(|cert| domain_matches(&cert.domain, request_url)));
+
+    if let Some(certificate) = certificate {
+        apply_certificate(&mut settings, certificate, &resolve);
+    }
+
+    settings
+}
+
+fn apply_certificate(settings: &mut TlsSettings, certificate: &ClientCertificate, resolve: &dyn Fn(&str) -> String) {
+    if certificate.cert_type == "pfx" {
+        settings.cert = certificate.pfx_file_path.as_deref().map(resolve);
+        settings.cert_type = Some("P12".to_string());
+    } else {
+        settings.cert = certificate.cert_file_path.as_deref().map(resolve);
+        settings.key = certificate.key_file_path.as_deref().map(resolve);
+    }
+    settings.passphrase = certificate.passphrase.clone().filter(|p| !p.is_empty());
+}
+
+/// Matches `domain` (which may use `*` and may carry a port) against the
+/// host of `url`. Without a port in `domain`, any port matches.
+fn domain_matches(domain: &str, url: &str) -> bool {
+    let domain = domain.trim().trim_start_matches("https://").trim_start_matches("http://");
+    let authority = url.split_once("://").map(|(_, rest)| rest).unwrap_or(url);
+    let authority = authority.split(['/', '?', '#']).next().unwrap_or_default();
+    let authority = authority.rsplit_once('@').map(|(_, host)| host).unwrap_or(authority).to_lowercase();
+    let domain = domain.to_lowercase();
+
+    if domain.contains(':') {
+        glob_match(&domain, &authority)
+    } else {
+        let host = authority.split(':').next().unwrap_or_default();
+        glob_match(&domain, host)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collection::parse_collection_config;
+    use crate::project::parse_project_config;
+
+    fn certificates(json: &str) -> ClientCertificates {
+        parse_collection_config(json).unwrap().client_certificates.unwrap()
+    }
+
+    #[test]
+    fn pem_certificate_for_matching_domain() {
+        let certs = certificates(
+            r#"{ "clientCertificates": { "enabled": true, "certs": [
+                { "domain": "*.internal.example.com", "type": "cert", "certFilePath": "certs/client.pem",
+                  "keyFilePath": "/etc/keys/client.key", "passphrase": "" } ] } }"#,
+        );
+        let settings = tls_settings(Some(&certs), &TlsDefaults::default(), "https://orders.internal.example.com/v1", Path::new("/repo"));
+        assert_eq!(settings.cert.as_deref(), Some("/repo/certs/client.pem"));
+        assert_eq!(settings.key.as_deref(), Some("/etc/keys/client.key"));
+        assert_eq!(settings.cert_type, None);
+        assert_eq!(settings.passphrase, None);
+        assert!(!settings.insecure);
+    }
+
+    #[test]
+    fn pfx_certificate_with_passphrase() {
+        let certs = certificates(
+            r#"{ "clientCertificates": { "enabled": true, "certs": [
+                { "domain": "api.example.com:8443", "type": "pfx", "pfxFilePath": "c.pfx", "passphrase": "pw" } ] } }"#,
+        );
+        let root = Path::new("/repo");
+        let settings = tls_settings(Some(&certs), &TlsDefaults::default(), "https://api.example.com:8443/x", root);
+        assert_eq!(settings.cert.as_deref(), Some("/repo/c.pfx"));
+        assert_eq!(settings.cert_type.as_deref(), Some("P12"));
+        assert_eq!(settings.passphrase.as_deref(), Some("pw"));
+
+        let other_port = tls_settings(Some(&certs), &TlsDefaults::default(), "https://api.example.com/x", root);
+        assert_eq!(other_port, TlsSettings::default());
+    }
+
+    #[test]
+    fn disabled_or_unmatched_certificates_are_ignored() {
+        let certs = certificates(
+            r#"{ "clientCertificates": { "enabled": false, "certs": [
+                { "domain": "*", "certFilePath": "c.pem", "keyFilePath": "c.key" } ] } }"#,
+        );
+        let settings = tls_settings(Some(&certs), &TlsDefaults::default(), "https://h", Path::new("/repo"));
+        assert_eq!(settings, TlsSettings::default());
+
+        assert!(!domain_matches("example.com", "https://example.com.evil.org/"));
+        assert!(domain_matches("EXAMPLE.com", "https://user@example.com:9000/path"));
+    }
+
+    #[test]
+    fn project_ca_cert_and_verification() {
+        let project = parse_project_config(r#"{ "tls": { "caCert": "certs/ca.pem", "sslVerification": false } }"#).unwrap();
+        let settings = tls_settings(None, &project.tls, "https://h", Path::new("/repo"));
+        assert_eq!(settings.ca_cert.as_deref(), Some("/repo/certs/ca.pem"));
+        assert!(settings.insecure);
+    }
+}